@@ -0,0 +1,115 @@
+//! # Advent of Code runner
+//!
+//! `aoc` knows about every day crate in the workspace through a registry and
+//! can run any of them from a single binary.
+
+pub mod scaffold;
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use utils::bench::Benchmark;
+use utils::{AocError, Part, Report, Solution};
+
+/// ## A registered day
+///
+/// The solution itself is type-erased so days with different input and output
+/// types can live in the same registry.
+pub struct Day {
+  pub number: u8,
+  pub title: &'static str,
+  pub run: fn(&str, &[Part]) -> Result<Report, AocError>,
+  pub bench: fn(&str, usize) -> Result<Benchmark, AocError>,
+}
+
+impl Day {
+  pub fn new<S: Solution>() -> Day {
+    Day {
+      number: S::DAY,
+      title: S::TITLE,
+      run: utils::run::<S>,
+      bench: utils::bench::run::<S>,
+    }
+  }
+
+  /// ### Directory of the day crate, containing its `input.txt`
+  pub fn dir(&self) -> PathBuf {
+    workspace_dir().join(format!("day_{}", self.number))
+  }
+
+  /// ### Default puzzle input of this day
+  pub fn input_file(&self) -> PathBuf {
+    self.dir().join("input.txt")
+  }
+
+  /// ### Directory with example inputs and their expected answers
+  pub fn examples_dir(&self) -> PathBuf {
+    self.dir().join("examples")
+  }
+
+  /// ### Expected answers for the default puzzle input
+  pub fn answers_file(&self) -> PathBuf {
+    self.dir().join("answers.txt")
+  }
+}
+
+/// ## Root directory of the workspace
+pub fn workspace_dir() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR"))
+    .parent()
+    .expect("The runner crate lives inside the workspace")
+    .to_path_buf()
+}
+
+/// ## File every benchmark run is appended to
+pub fn history_file() -> PathBuf {
+  workspace_dir().join("bench_history.tsv")
+}
+
+/// ## Short hash of the checked out git revision, if there is one
+pub fn git_revision() -> Option<String> {
+  let output = Command::new("git")
+    .args(["rev-parse", "--short", "HEAD"])
+    .current_dir(workspace_dir())
+    .output()
+    .ok()?;
+  if output.status.success() {
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+  } else {
+    None
+  }
+}
+
+/// ## All registered days, in order
+pub fn days() -> Vec<Day> {
+  vec![
+    Day::new::<day_1::Day1>(),
+    Day::new::<day_2::Day2>(),
+    Day::new::<day_3::Day3>(),
+    Day::new::<day_4::Day4>(),
+    Day::new::<day_5::Day5>(),
+    Day::new::<day_6::Day6>(),
+    Day::new::<day_7::Day7>(),
+    Day::new::<day_8::Day8>(),
+    Day::new::<day_9::Day9>(),
+    Day::new::<day_10::Day10>(),
+    Day::new::<day_11::Day11>(),
+    Day::new::<day_12::Day12>(),
+    Day::new::<day_13::Day13>(),
+    Day::new::<day_14::Day14>(),
+    Day::new::<day_15::Day15>(),
+    Day::new::<day_16::Day16>(),
+    Day::new::<day_17::Day17>(),
+    Day::new::<day_18::Day18>(),
+  ]
+}
+
+/// ## Look up a single day in the registry
+///
+/// ### Example
+/// ```
+/// assert_eq!( aoc::find( 3 ).map( |day| day.number ), Some( 3 ) );
+/// assert!( aoc::find( 42 ).is_none() );
+/// ```
+pub fn find(number: u8) -> Option<Day> {
+  days().into_iter().find(|day| day.number == number)
+}
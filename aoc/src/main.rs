@@ -0,0 +1,501 @@
+extern crate aoc;
+extern crate utils;
+
+use aoc::Day;
+use std::env;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+use utils::bench::format_duration;
+use utils::history::{self, Record};
+use utils::{alloc, Answers, Part, Timed, Verdict};
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAllocator = alloc::CountingAllocator;
+
+const USAGE: &str = "\
+Usage: aoc run <day|all> [--part <1|2>] [input file]
+       aoc verify <day|all>
+       aoc bench <day|all> [--iterations <n>] [--format <table|json>] [--label <name>]
+       aoc compare [--baseline <run|revision>] [--threshold <percent>]
+       aoc new <day> [--title <title>]";
+
+/// Which days to run and with which input
+struct RunArgs {
+  days: Vec<Day>,
+  part: Option<Part>,
+  input_file: Option<String>,
+}
+
+fn main() {
+  let args: Vec<String> = env::args().skip(1).collect();
+
+  let result = match args.first().map(String::as_str) {
+    Some("run") => parse_run_args(&args[1..]).map(|run_args| run(&run_args)),
+    Some("bench") => parse_bench_args(&args[1..]).map(|bench_args| bench(&bench_args)),
+    Some("compare") => parse_compare_args(&args[1..]).map(|compare_args| compare(&compare_args)),
+    Some("new") => parse_new_args(&args[1..]).map(|(day, title)| new(day, &title)),
+    Some("verify") => match &args[1..] {
+      [selection] => parse_selection(selection).map(|days| verify(&days)),
+      _ => Err(USAGE.to_string()),
+    },
+    _ => Err(USAGE.to_string()),
+  };
+
+  match result {
+    Ok(true) => (),
+    Ok(false) => process::exit(1),
+    Err(message) => {
+      eprintln!("{}", message);
+      process::exit(2);
+    }
+  }
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+  let mut selection = None;
+  let mut part = None;
+  let mut input_file = None;
+
+  let mut args = args.iter();
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--part" | "-p" => {
+        let value = args.next().ok_or("--part expects a value")?;
+        part = match value.as_str() {
+          "1" => Some(Part::One),
+          "2" => Some(Part::Two),
+          _ => return Err(format!("Unknown part {}, expected 1 or 2", value)),
+        };
+      }
+      _ if selection.is_none() => selection = Some(arg.clone()),
+      _ if input_file.is_none() => input_file = Some(arg.clone()),
+      _ => return Err(format!("Unexpected argument {}\n{}", arg, USAGE)),
+    }
+  }
+
+  let days = parse_selection(&selection.ok_or(USAGE)?)?;
+
+  if input_file.is_some() && days.len() > 1 {
+    return Err("An input file can only be passed when running a single day".to_string());
+  }
+
+  Ok(RunArgs {
+    days,
+    part,
+    input_file,
+  })
+}
+
+/// Time, and memory when counting allocations, used to compute a value
+fn usage<T>(timed: &Timed<T>) -> String {
+  if alloc::is_counting() {
+    format!("{:?}, {}", timed.elapsed, timed.memory)
+  } else {
+    format!("{:?}", timed.elapsed)
+  }
+}
+
+fn parse_new_args(args: &[String]) -> Result<(u8, String), String> {
+  let (day, title) = match args {
+    [day] => (day, None),
+    [day, flag, title] if flag == "--title" || flag == "-t" => (day, Some(title.clone())),
+    _ => return Err(USAGE.to_string()),
+  };
+
+  let day = day
+    .parse::<u8>()
+    .map_err(|_| format!("Invalid day {}", day))?;
+  Ok((day, title.unwrap_or_else(|| format!("Day {}", day))))
+}
+
+/// Read a puzzle input, which is `None` while it's still empty as created by
+/// `aoc new`
+fn read_input(input_file: &str) -> io::Result<Option<String>> {
+  let data = utils::load_file(input_file)?;
+  Ok(Some(data).filter(|data| !data.trim().is_empty()))
+}
+
+/// Select either a single day or all of them
+fn parse_selection(selection: &str) -> Result<Vec<Day>, String> {
+  match selection {
+    "all" => Ok(aoc::days()),
+    number => number
+      .parse::<u8>()
+      .ok()
+      .and_then(aoc::find)
+      .map(|day| vec![day])
+      .ok_or_else(|| format!("Unknown day {}", number)),
+  }
+}
+
+/// Which days to benchmark and how to report the results
+struct BenchArgs {
+  days: Vec<Day>,
+  iterations: usize,
+  json: bool,
+  label: Option<String>,
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+  let mut selection = None;
+  let mut iterations = 10;
+  let mut json = false;
+  let mut label = None;
+
+  let mut args = args.iter();
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--iterations" | "-n" => {
+        let value = args.next().ok_or("--iterations expects a value")?;
+        iterations = match value.parse::<usize>() {
+          Ok(count) if count > 0 => count,
+          _ => return Err(format!("Invalid number of iterations {}", value)),
+        };
+      }
+      "--format" | "-f" => {
+        json = match args.next().map(String::as_str) {
+          Some("table") => false,
+          Some("json") => true,
+          _ => return Err("--format expects either table or json".to_string()),
+        };
+      }
+      "--label" | "-l" => {
+        let value = args.next().ok_or("--label expects a name")?;
+        if value.is_empty() || value.contains(char::is_whitespace) {
+          return Err(format!("Invalid label {:?}", value));
+        }
+        label = Some(value.clone());
+      }
+      _ if selection.is_none() => selection = Some(arg.clone()),
+      _ => return Err(format!("Unexpected argument {}\n{}", arg, USAGE)),
+    }
+  }
+
+  Ok(BenchArgs {
+    days: parse_selection(&selection.ok_or(USAGE)?)?,
+    iterations,
+    json,
+    label,
+  })
+}
+
+/// Which run to compare the latest benchmark run with
+struct CompareArgs {
+  baseline: Option<String>,
+  threshold: f64,
+}
+
+fn parse_compare_args(args: &[String]) -> Result<CompareArgs, String> {
+  let mut baseline = None;
+  let mut threshold = 10.0;
+
+  let mut args = args.iter();
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--baseline" | "-b" => {
+        baseline = Some(args.next().ok_or("--baseline expects a run or revision")?.clone());
+      }
+      "--threshold" | "-t" => {
+        let value = args.next().ok_or("--threshold expects a percentage")?;
+        threshold = match value.trim_end_matches('%').parse::<f64>() {
+          Ok(percent) if percent >= 0.0 => percent,
+          _ => return Err(format!("Invalid threshold {}", value)),
+        };
+      }
+      _ => return Err(format!("Unexpected argument {}\n{}", arg, USAGE)),
+    }
+  }
+
+  Ok(CompareArgs {
+    baseline,
+    threshold,
+  })
+}
+
+/// Run all selected days, returns `false` if one of them couldn't be run
+fn run(args: &RunArgs) -> bool {
+  let mut success = true;
+
+  for day in &args.days {
+    let input_file = args
+      .input_file
+      .clone()
+      .unwrap_or_else(|| day.input_file().display().to_string());
+
+    println!("==== [AOC] Day {}: {} ====", day.number, day.title);
+    println!("Reading data from {}", input_file);
+
+    let data = match read_input(&input_file) {
+      Ok(Some(data)) => data,
+      Ok(None) => {
+        eprintln!("Input file is empty, skipping");
+        continue;
+      }
+      Err(err) => {
+        eprintln!("Couldn't read input file: {}", err);
+        success = false;
+        continue;
+      }
+    };
+
+    let parts = match args.part {
+      Some(part) => vec![part],
+      None => Part::ALL.to_vec(),
+    };
+
+    let report = match (day.run)(&data, &parts) {
+      Ok(report) => report,
+      Err(err) => {
+        eprint!("{}", err.render(&input_file, &data));
+        success = false;
+        continue;
+      }
+    };
+    println!("Parsed input in {}", usage(&report.parse));
+
+    for (part, answer) in report.parts {
+      println!("--- {} ---", part);
+      match &answer.value {
+        Ok(value) => println!("Result: {} ({})", value, usage(&answer)),
+        Err(err) => {
+          eprintln!("Error: {}", err);
+          success = false;
+        }
+      }
+    }
+  }
+
+  success
+}
+
+/// Check all parts of the selected days against their expected answers,
+/// returns `false` if an answer is wrong or couldn't be computed
+fn verify(days: &[Day]) -> bool {
+  let mut success = true;
+  let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+  for day in days {
+    let input_file = day.input_file().display().to_string();
+    let data = match read_input(&input_file) {
+      Ok(Some(data)) => data,
+      Ok(None) => {
+        eprintln!("Day {}: {} is empty, skipping", day.number, input_file);
+        continue;
+      }
+      Err(err) => {
+        eprintln!("Day {}: couldn't read {}: {}", day.number, input_file, err);
+        success = false;
+        continue;
+      }
+    };
+
+    let answers_file = day.answers_file().display().to_string();
+    let answers = match fs::read_to_string(&answers_file) {
+      Ok(text) => match Answers::parse(&text) {
+        Ok(answers) => answers,
+        Err(err) => {
+          eprint!("{}", err.render(&answers_file, &text));
+          success = false;
+          continue;
+        }
+      },
+      Err(ref err) if err.kind() == ErrorKind::NotFound => Answers::default(),
+      Err(err) => {
+        eprintln!("Day {}: couldn't read {}: {}", day.number, answers_file, err);
+        success = false;
+        continue;
+      }
+    };
+
+    let report = match (day.run)(&data, &Part::ALL) {
+      Ok(report) => report,
+      Err(err) => {
+        eprint!("{}", err.render(&input_file, &data));
+        success = false;
+        continue;
+      }
+    };
+
+    for (part, answer) in report.parts {
+      let verdict = answers.verdict(part, answer.value);
+      match verdict {
+        Verdict::Pass(_) => passed += 1,
+        Verdict::Missing(_) => missing += 1,
+        Verdict::Fail { .. } | Verdict::Error(_) => {
+          failed += 1;
+          success = false;
+        }
+      }
+      println!("Day {:>2}, {}: {}", day.number, part, verdict);
+    }
+  }
+
+  println!("{} passed, {} failed, {} missing", passed, failed, missing);
+  success
+}
+
+/// Benchmark the selected days on their default input, returns `false` if one
+/// of them couldn't be run
+fn bench(args: &BenchArgs) -> bool {
+  let mut success = true;
+  let mut benchmarks = Vec::new();
+  let mut records = Vec::new();
+
+  let run = args.label.clone().unwrap_or_else(|| {
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap_or_default();
+    format!("run-{}", now.as_millis())
+  });
+  let revision = aoc::git_revision().unwrap_or_else(|| "unknown".to_string());
+
+  if args.label.is_some() && !is_new_run(&run) {
+    return false;
+  }
+
+  for day in &args.days {
+    let input_file = day.input_file().display().to_string();
+    let data = match read_input(&input_file) {
+      Ok(Some(data)) => data,
+      Ok(None) => {
+        eprintln!("Day {}: {} is empty, skipping", day.number, input_file);
+        continue;
+      }
+      Err(err) => {
+        eprintln!("Day {}: couldn't read {}: {}", day.number, input_file, err);
+        success = false;
+        continue;
+      }
+    };
+
+    match (day.bench)(&data, args.iterations) {
+      Ok(benchmark) => {
+        let input_hash = history::input_hash(&data);
+        records.extend(benchmark.measurements.iter().map(|measurement| Record {
+          run: run.clone(),
+          revision: revision.clone(),
+          input_hash: input_hash.clone(),
+          day: benchmark.day,
+          stage: measurement.stage,
+          stats: measurement.stats.clone(),
+        }));
+        benchmarks.push(benchmark);
+      }
+      Err(err) => {
+        eprint!("{}", err.render(&input_file, &data));
+        success = false;
+      }
+    }
+  }
+
+  if args.json {
+    println!("{}", utils::bench::json(&benchmarks));
+  } else {
+    print!("{}", utils::bench::table(&benchmarks));
+  }
+
+  if !records.is_empty() {
+    if let Err(err) = history::append(&aoc::history_file(), &records) {
+      eprintln!("Couldn't save benchmark history: {}", err);
+      success = false;
+    }
+  }
+  success
+}
+
+/// Check that the history has no run named `run` yet, so a reused label
+/// doesn't merge into an earlier run
+fn is_new_run(run: &str) -> bool {
+  let history_file = aoc::history_file().display().to_string();
+  let content = match fs::read_to_string(&history_file) {
+    Ok(content) => content,
+    Err(err) if err.kind() == ErrorKind::NotFound => return true,
+    Err(err) => {
+      eprintln!("Couldn't read {}: {}", history_file, err);
+      return false;
+    }
+  };
+
+  match history::parse(&content).and_then(|records| history::ensure_new_run(&records, run)) {
+    Ok(()) => true,
+    Err(err) => {
+      eprint!("{}", err.render(&history_file, &content));
+      false
+    }
+  }
+}
+
+/// Compare the latest benchmark run with a baseline, returns `false` if a
+/// stage regressed beyond the threshold
+fn compare(args: &CompareArgs) -> bool {
+  let history_file = aoc::history_file().display().to_string();
+  let content = match fs::read_to_string(&history_file) {
+    Ok(content) => content,
+    Err(err) => {
+      eprintln!("Couldn't read {}: {}", history_file, err);
+      return false;
+    }
+  };
+
+  let comparison = history::parse(&content)
+    .and_then(|records| history::compare(&records, args.baseline.as_deref()));
+  let (current, comparisons) = match comparison {
+    Ok(comparison) => comparison,
+    Err(err) => {
+      eprint!("{}", err.render(&history_file, &content));
+      return false;
+    }
+  };
+
+  println!("Comparing {} with earlier runs", current);
+  println!(
+    "{:>3}  {:<6}  {:<20}  {:>10}  {:>10}  {:>8}",
+    "day", "stage", "baseline run", "baseline", "current", "change"
+  );
+
+  let threshold = args.threshold / 100.0;
+  let mut regressions = 0;
+  for comparison in &comparisons {
+    let regressed = comparison.is_regression(threshold);
+    if regressed {
+      regressions += 1;
+    }
+    println!(
+      "{:>3}  {:<6}  {:<20}  {:>10}  {:>10}  {:>+7.1}%{}",
+      comparison.day,
+      comparison.stage.to_string(),
+      comparison.baseline_run,
+      format_duration(comparison.baseline),
+      format_duration(comparison.current),
+      comparison.change() * 100.0,
+      if regressed { "  REGRESSION" } else { "" }
+    );
+  }
+
+  println!(
+    "{} of {} stages regressed by more than {}%",
+    regressions,
+    comparisons.len(),
+    args.threshold
+  );
+  regressions == 0
+}
+
+/// Scaffold the crate of a new day, returns `false` if it couldn't be created
+fn new(day: u8, title: &str) -> bool {
+  match aoc::scaffold::create(day, title) {
+    Ok(files) => {
+      for file in files {
+        println!("Wrote {}", file.display());
+      }
+      true
+    }
+    Err(err) => {
+      eprintln!("Couldn't create day {}: {}", day, err);
+      false
+    }
+  }
+}
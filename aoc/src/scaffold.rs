@@ -0,0 +1,282 @@
+//! # Scaffolding of new day crates
+//!
+//! A new day gets a crate modelled after the existing ones, with an empty
+//! input and example, and is registered in the workspace, in the runner's
+//! dependencies and in the registry.
+
+use crate::workspace_dir;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use utils::{AocError, Span};
+
+/// ## Create and register the crate of a new day
+///
+/// Returns the files that were created or changed.
+///
+/// ### Errors
+/// Fails if the day is out of range or its crate already exists, before
+/// anything is written.  When writing fails halfway, the crate is removed and
+/// the registrations are restored.
+pub fn create(day: u8, title: &str) -> Result<Vec<PathBuf>, AocError> {
+  if day == 0 || day > 25 {
+    return Err(AocError::InvalidInput(format!("there is no day {}", day)));
+  }
+
+  let dir = workspace_dir().join(format!("day_{}", day));
+  if dir.exists() {
+    return Err(AocError::InvalidInput(format!("{} already exists", dir.display())));
+  }
+
+  let workspace_manifest = workspace_dir().join("Cargo.toml");
+  let runner_manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+  let registry = Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("lib.rs");
+  let registrations = [workspace_manifest, runner_manifest, registry];
+  let originals = registrations
+    .iter()
+    .map(fs::read_to_string)
+    .collect::<Result<Vec<_>, _>>()?;
+
+  // Prepare every change first, so nothing is written when one of them fails
+  let changes = vec![
+    (registrations[0].clone(), register_member(&originals[0], day)?),
+    (registrations[1].clone(), register_dependency(&originals[1], day)?),
+    (registrations[2].clone(), register_day(&originals[2], day)?),
+  ];
+
+  let files = vec![
+    (dir.join("Cargo.toml"), cargo_toml(day)),
+    (dir.join("src").join("lib.rs"), lib_rs(day, title)),
+    (dir.join("input.txt"), String::new()),
+    (dir.join("examples").join("example.txt"), String::new()),
+  ];
+
+  let mut written = Vec::new();
+  for (path, content) in files.into_iter().chain(changes) {
+    if let Err(err) = write(&path, &content) {
+      // Undo what was written so far, without hiding the original error
+      let _ = fs::remove_dir_all(&dir);
+      for (path, original) in registrations.iter().zip(&originals) {
+        let _ = fs::write(path, original);
+      }
+      return Err(err.into());
+    }
+    written.push(path);
+  }
+  Ok(written)
+}
+
+fn write(path: &Path, content: &str) -> io::Result<()> {
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  fs::write(path, content)
+}
+
+/// ## Manifest of a new day crate
+pub fn cargo_toml(day: u8) -> String {
+  format!(
+    r#"[package]
+name = "day_{}"
+version = "0.1.0"
+authors = ["Rutger Claes <rutger.claes@datylon.com>"]
+edition = "2018"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+utils = {{ path = "../utils" }}
+"#,
+    day
+  )
+}
+
+/// ## Stub solution of a new day crate
+///
+/// Both parts report that they aren't implemented yet, so the runner keeps
+/// working for the other days.
+pub fn lib_rs(day: u8, title: &str) -> String {
+  format!(
+    r#"//! # Day {day} of Advent of Code
+//!
+//! `day_{day}` contains the solution to the puzzle of
+//! [day {day}](https://adventofcode.com/2018/day/{day}) of the 2018 edition of Advent of Code.
+
+use utils::{{AocError, Solution}};
+
+/// ## Part 1
+pub fn part_1(_lines: &[&str]) -> Result<usize, AocError> {{
+  Err(AocError::NoSolution("not implemented yet".to_string()))
+}}
+
+/// ## Part 2
+pub fn part_2(_lines: &[&str]) -> Result<usize, AocError> {{
+  Err(AocError::NoSolution("not implemented yet".to_string()))
+}}
+
+/// # {title}
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+  const DAY: u8 = {day};
+  const TITLE: &'static str = {title:?};
+
+  type Input<'a> = Vec<&'a str>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Vec<&str>, AocError> {{
+    Ok(input.lines().collect())
+  }}
+
+  fn part_1(lines: &Vec<&str>) -> Result<usize, AocError> {{
+    part_1(lines)
+  }}
+
+  fn part_2(lines: &Vec<&str>) -> Result<usize, AocError> {{
+    part_2(lines)
+  }}
+}}
+"#,
+    day = day,
+    title = title
+  )
+}
+
+/// ## Add a day crate to the workspace members
+///
+/// The member is added in order of the days.
+///
+/// ### Example
+/// ```
+/// let manifest = "[workspace]\n\nmembers = [ 'utils', 'aoc', 'day_1' ]\n";
+/// assert_eq!(
+///   aoc::scaffold::register_member( manifest, 2 ).unwrap(),
+///   "[workspace]\n\nmembers = [ 'utils', 'aoc', 'day_1', 'day_2' ]\n"
+/// );
+/// assert!( aoc::scaffold::register_member( manifest, 1 ).is_err() );
+///
+/// let manifest = "members = [ 'utils', 'aoc', 'day_6', 'day_18' ]\n";
+/// assert_eq!(
+///   aoc::scaffold::register_member( manifest, 7 ).unwrap(),
+///   "members = [ 'utils', 'aoc', 'day_6', 'day_7', 'day_18' ]\n"
+/// );
+/// ```
+pub fn register_member(manifest: &str, day: u8) -> Result<String, AocError> {
+  let member = format!("'day_{}'", day);
+  let start = manifest
+    .find("members = [")
+    .ok_or_else(|| AocError::InvalidInput("no workspace members in Cargo.toml".to_string()))?;
+  let end = start
+    + manifest[start..]
+      .find(']')
+      .ok_or_else(|| AocError::InvalidInput("unterminated workspace members".to_string()))?;
+  let members = &manifest[start..end];
+
+  if members.contains(&member) {
+    return Err(AocError::InvalidInput(format!("{} is already a workspace member", member)));
+  }
+
+  let later = members
+    .split(',')
+    .map(str::trim)
+    .find(|existing| day_of(existing).is_some_and(|other| other > day));
+  match later {
+    Some(later) => {
+      let insert_at = Span::of(manifest, later).start;
+      Ok(format!("{}{}, {}", &manifest[..insert_at], member, &manifest[insert_at..]))
+    }
+    None => {
+      let insert_at = start + members.trim_end().len();
+      Ok(format!("{}, {}{}", &manifest[..insert_at], member, &manifest[insert_at..]))
+    }
+  }
+}
+
+/// ## Add a day crate to the dependencies of the runner
+///
+/// The dependency is added in order of the days.
+///
+/// ### Example
+/// ```
+/// let manifest = "[dependencies]\nutils = { path = \"../utils\" }\nday_1 = { path = \"../day_1\" }\n";
+/// assert_eq!(
+///   aoc::scaffold::register_dependency( manifest, 2 ).unwrap(),
+///   "[dependencies]\nutils = { path = \"../utils\" }\nday_1 = { path = \"../day_1\" }\nday_2 = { path = \"../day_2\" }\n"
+/// );
+///
+/// let manifest = "day_6 = { path = \"../day_6\" }\nday_18 = { path = \"../day_18\" }\n";
+/// assert_eq!(
+///   aoc::scaffold::register_dependency( manifest, 7 ).unwrap(),
+///   "day_6 = { path = \"../day_6\" }\nday_7 = { path = \"../day_7\" }\nday_18 = { path = \"../day_18\" }\n"
+/// );
+/// ```
+pub fn register_dependency(manifest: &str, day: u8) -> Result<String, AocError> {
+  insert_in_order(
+    day,
+    manifest,
+    "day_",
+    &format!("day_{0} = {{ path = \"../day_{0}\" }}", day),
+  )
+}
+
+/// ## Add a day to the registry of the runner
+///
+/// The day is added in order of the days.
+///
+/// ### Example
+/// ```
+/// let registry = "  vec![\n    Day::new::<day_1::Day1>(),\n  ]\n";
+/// assert_eq!(
+///   aoc::scaffold::register_day( registry, 2 ).unwrap(),
+///   "  vec![\n    Day::new::<day_1::Day1>(),\n    Day::new::<day_2::Day2>(),\n  ]\n"
+/// );
+///
+/// let registry = "    Day::new::<day_6::Day6>(),\n    Day::new::<day_18::Day18>(),\n";
+/// assert_eq!(
+///   aoc::scaffold::register_day( registry, 7 ).unwrap(),
+///   "    Day::new::<day_6::Day6>(),\n    Day::new::<day_7::Day7>(),\n    Day::new::<day_18::Day18>(),\n"
+/// );
+/// ```
+pub fn register_day(registry: &str, day: u8) -> Result<String, AocError> {
+  insert_in_order(
+    day,
+    registry,
+    "    Day::new::<",
+    &format!("    Day::new::<day_{0}::Day{0}>(),", day),
+  )
+}
+
+/// Day of the first day crate named in `text`
+fn day_of(text: &str) -> Option<u8> {
+  let (_, rest) = text.split_once("day_")?;
+  let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+  rest[..digits].parse().ok()
+}
+
+/// Insert `line` among the lines starting with `prefix`, before the first
+/// one of a later day or else after the last one
+fn insert_in_order(day: u8, content: &str, prefix: &str, line: &str) -> Result<String, AocError> {
+  if content.lines().any(|existing| existing.trim() == line.trim()) {
+    return Err(AocError::InvalidInput(format!("{:?} is already present", line.trim())));
+  }
+
+  let days: Vec<&str> = content
+    .lines()
+    .filter(|existing| existing.starts_with(prefix))
+    .collect();
+  let last = days
+    .last()
+    .ok_or_else(|| AocError::InvalidInput(format!("no line starting with {:?}", prefix)))?;
+
+  match days.iter().find(|existing| day_of(existing).is_some_and(|other| other > day)) {
+    Some(later) => {
+      let start = Span::of(content, later).start;
+      Ok(format!("{}{}\n{}", &content[..start], line, &content[start..]))
+    }
+    None => {
+      let end = Span::of(content, last).end;
+      Ok(format!("{}\n{}{}", &content[..end], line, &content[end..]))
+    }
+  }
+}
@@ -0,0 +1,66 @@
+//! Runs every example in the `examples` directory of each registered day and
+//! checks the answers recorded in it.
+
+use std::fs;
+use utils::{Answers, Part, Span};
+
+#[test]
+fn examples() {
+  let mut checked = 0;
+  let mut failures = Vec::new();
+
+  for day in aoc::days() {
+    let mut files: Vec<_> = match fs::read_dir(day.examples_dir()) {
+      Ok(entries) => entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect(),
+      Err(_) => continue,
+    };
+    files.sort();
+
+    for file in files {
+      let name = file.display().to_string();
+      let example = fs::read_to_string(&file).unwrap();
+      if example.trim().is_empty() {
+        // Placeholder created by `aoc new`
+        continue;
+      }
+      let (answers, input) = match utils::split_example(&example) {
+        Ok(split) => split,
+        Err(err) => {
+          failures.push(err.render(&name, &example));
+          continue;
+        }
+      };
+      if answers == Answers::default() {
+        failures.push(format!("{}: no expected answers", name));
+        continue;
+      }
+
+      let parts: Vec<Part> = Part::ALL
+        .iter()
+        .copied()
+        .filter(|&part| answers.get(part).is_some())
+        .collect();
+      match (day.run)(input, &parts) {
+        Ok(report) => {
+          for (part, answer) in report.parts {
+            let verdict = answers.verdict(part, answer.value);
+            if verdict.is_failure() {
+              failures.push(format!("{}, {}: {}", name, part, verdict));
+            }
+            checked += 1;
+          }
+        }
+        Err(err) => {
+          let err = err.within(Span::of(&example, input));
+          failures.push(err.render(&name, &example));
+        }
+      }
+    }
+  }
+
+  assert!(checked > 0, "no examples found");
+  assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
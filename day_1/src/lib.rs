@@ -4,6 +4,7 @@
 //! [Advent of Code](https://adventofcode.com/2018/day/1)
 
 use std::collections::HashSet;
+use utils::{AocError, Solution};
 
 /// ## Part 1
 /// Given a list of frequency changes, and starting from 0, calculate the
@@ -15,8 +16,8 @@ use std::collections::HashSet;
 /// let input = vec![1, -2, 3, 1 ];
 /// assert_eq!( day_1::part_1( &input ), 3 );
 /// ```
-pub fn part_1(deltas: &Vec<i32>) -> i32 {
-  deltas.iter().fold(0, |freq, delta| freq + delta)
+pub fn part_1(deltas: &[i32]) -> i32 {
+  deltas.iter().sum()
 }
 
 /// ## Part 2
@@ -26,12 +27,30 @@ pub fn part_1(deltas: &Vec<i32>) -> i32 {
 /// ### Example
 /// ```
 /// let input = vec![1, -2, 3, 1 ];
-/// assert_eq!( day_1::part_2( &input ), 2 );
+/// assert_eq!( day_1::part_2( &input ).unwrap(), 2 );
+///
+/// let input = vec![1, 1];
+/// assert!( day_1::part_2( &input ).is_err() );
 /// ```
-pub fn part_2(deltas: &Vec<i32>) -> i32 {
+///
+/// ### Errors
+/// Fails if no frequency is ever reached twice.
+pub fn part_2(deltas: &[i32]) -> Result<i32, AocError> {
+  // Once the frequencies drifted further than the range covered by a single
+  // pass, no new repetitions can be found.
+  let drift = part_1(deltas).abs();
+  let (min, max) = deltas
+    .iter()
+    .scan(0, |freq, delta| {
+      *freq += delta;
+      Some(*freq)
+    })
+    .fold((0, 0), |(min, max), freq| (freq.min(min), freq.max(max)));
+  let passes = if drift == 0 { 1 } else { (max - min) / drift + 2 };
+
   let mut initial = HashSet::new();
   initial.insert(0);
-  let mut duplicates = deltas
+  let duplicates = deltas
     .iter()
     .cycle()
     .scan((initial, 0), |(acc, freq), delta| {
@@ -48,24 +67,56 @@ pub fn part_2(deltas: &Vec<i32>) -> i32 {
       }
     });
 
-  duplicates.find(|v| v.is_some()).unwrap().unwrap()
+  duplicates
+    .take(deltas.len() * passes as usize)
+    .find_map(|duplicate| duplicate)
+    .ok_or_else(|| AocError::NoSolution("no frequency is reached twice".to_string()))
 }
 
-/// # Read a vector of strings as integers
+/// # Read every line of the input as an integer
 ///
 /// ### Example
 /// ```
-/// let input = vec![ "-1", "1", "+1", "-10" ];
-/// assert_eq!( day_1::read_integers( &input ), vec![ -1, 1, 1, -10 ] );
+/// let input = "-1\n1\n+1\n-10";
+/// assert_eq!( day_1::read_integers( input ).unwrap(), vec![ -1, 1, 1, -10 ] );
+///
+/// let input = "-1\none";
+/// assert_eq!( day_1::read_integers( input ).unwrap_err().to_string(), "invalid integer \"one\" (bytes 3..6)" );
 /// ```
 ///
-/// ### Panics
-/// Panics if one of the input values cannot be parsed as `i32`.
-pub fn read_integers(input: &Vec<&str>) -> Vec<i32> {
-  input
-    .iter()
-    .map(|line| line.parse::<i32>().unwrap())
-    .collect()
+/// ### Errors
+/// Fails if one of the input values cannot be parsed as `i32`, the error
+/// points at the offending line.
+pub fn read_integers(input: &str) -> Result<Vec<i32>, AocError> {
+  utils::parse_lines(input, |line| {
+    line
+      .parse::<i32>()
+      .map_err(|_| AocError::parse(format!("invalid integer {:?}", line)))
+  })
+}
+
+/// # Chronal Calibration
+pub struct Day1;
+
+impl Solution for Day1 {
+  const DAY: u8 = 1;
+  const TITLE: &'static str = "Chronal Calibration";
+
+  type Input<'a> = Vec<i32>;
+  type Output1 = i32;
+  type Output2 = i32;
+
+  fn parse(input: &str) -> Result<Vec<i32>, AocError> {
+    read_integers(input)
+  }
+
+  fn part_1(deltas: &Vec<i32>) -> Result<i32, AocError> {
+    Ok(part_1(deltas))
+  }
+
+  fn part_2(deltas: &Vec<i32>) -> Result<i32, AocError> {
+    part_2(deltas)
+  }
 }
 
 #[cfg(test)]
@@ -91,25 +142,35 @@ mod tests {
   #[test]
   fn test_part_2() {
     let test_data = vec![1, -2, 3, 1];
-    assert_eq!(part_2(&test_data), 2);
+    assert_eq!(part_2(&test_data).unwrap(), 2);
 
     let test_data = vec![1, -1];
-    assert_eq!(part_2(&test_data), 0);
+    assert_eq!(part_2(&test_data).unwrap(), 0);
 
     let test_data = vec![3, 3, 4, -2, -4];
-    assert_eq!(part_2(&test_data), 10);
+    assert_eq!(part_2(&test_data).unwrap(), 10);
 
     let test_data = vec![-6, 3, 8, 5, -6];
-    assert_eq!(part_2(&test_data), 5);
+    assert_eq!(part_2(&test_data).unwrap(), 5);
 
     let test_data = vec![7, 7, -2, -7, -4];
-    assert_eq!(part_2(&test_data), 14);
+    assert_eq!(part_2(&test_data).unwrap(), 14);
+
+    let test_data = vec![];
+    assert!(part_2(&test_data).is_err());
   }
 
   #[test]
   fn test_read_integers() {
-    let test_data = vec!["+1", "-2", "3", "1"];
-    assert_eq!(read_integers(&test_data), vec![1, -2, 3, 1]);
+    let test_data = "+1\n-2\n3\n1";
+    assert_eq!(read_integers(test_data).unwrap(), vec![1, -2, 3, 1]);
+
+    let test_data = "+1\n-2\n+x\n1";
+    let error = read_integers(test_data).unwrap_err();
+    assert_eq!(
+      error.render("input.txt", test_data),
+      "error: invalid integer \"+x\"\n --> input.txt:3:1\n  |\n3 | +x\n  | ^^\n"
+    );
   }
 
 }
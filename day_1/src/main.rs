@@ -1,24 +0,0 @@
-extern crate day_1;
-extern crate utils;
-
-use std::env;
-
-fn main() {
-    let input_file = env::args()
-        .skip(1)
-        .next()
-        .expect("Pass the input file as first parameter");
-
-    println!("==== [AOC] Day 1 ====");
-    println!("Reading data from {}", input_file);
-
-    let data = utils::load_file(&input_file).expect("Couldn't read input file");
-    let lines = data.lines().collect();
-    let numbers = day_1::read_integers(&lines);
-
-    println!("--- Part 1 ---");
-    println!("Result: {}", day_1::part_1(&numbers));
-
-    println!("--- Part 2 ---");
-    println!("Result: {}", day_1::part_2(&numbers));
-}
@@ -0,0 +1,260 @@
+//! # Day 10 of Advent of Code
+//!
+//! `day_10` contains the solution to the puzzle of
+//! [day 10](https://adventofcode.com/2018/day/10) of the 2018 edition of Advent of Code.
+
+use utils::{parse_lines, AocError, Solution, Span};
+
+/// ## Letters of the puzzle, as rendered by `render`
+///
+/// Every letter is a block of the letter and its rows, separated by blank
+/// lines.  The letters of the puzzle input are 10 rows high, those of the
+/// example in the puzzle text 8.
+const FONT: &str = include_str!("font.txt");
+
+/// Letters of the font with their rows
+fn letters() -> impl Iterator<Item = (char, Vec<&'static str>)> {
+  FONT.split("\n\n").filter_map(|block| {
+    let mut rows = block.lines();
+    let letter = rows.next()?.chars().next()?;
+    Some((letter, rows.collect()))
+  })
+}
+
+/// ## A point of light with its position at second 0
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Light {
+  pub position: (i64, i64),
+  pub velocity: (i64, i64),
+}
+
+impl Light {
+  /// ## Parse an input line into a point of light
+  ///
+  /// ### Example
+  /// ```
+  /// use day_10::Light;
+  ///
+  /// assert_eq!(
+  ///   Light::parse( "position=< 9,  1> velocity=< 0, -2>" ).unwrap(),
+  ///   Light { position: ( 9, 1 ), velocity: ( 0, -2 ) }
+  /// );
+  ///
+  /// let error = Light::parse( "position=< 9,  1> velocity=< 0, -z>" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid number \"-z\" (bytes 32..34)" );
+  ///
+  /// assert!( Light::parse( "position=< 9,  1>" ).is_err() );
+  /// ```
+  pub fn parse(line: &str) -> Result<Light, AocError> {
+    let invalid = || AocError::parse(format!("invalid point of light \"{}\"", line));
+
+    let rest = line.strip_prefix("position=<").ok_or_else(invalid)?;
+    let (position, rest) = rest.split_once("> velocity=<").ok_or_else(invalid)?;
+    let velocity = rest.strip_suffix('>').ok_or_else(invalid)?;
+
+    let pair = |field: &str| -> Result<(i64, i64), AocError> {
+      let (x, y) = field.split_once(',').ok_or_else(invalid)?;
+      let number = |field: &str| {
+        let field = field.trim();
+        field.parse().map_err(|_| {
+          AocError::parse_at(
+            Span::of(line, field),
+            format!("invalid number \"{}\"", field),
+          )
+        })
+      };
+      Ok((number(x)?, number(y)?))
+    };
+
+    Ok(Light {
+      position: pair(position)?,
+      velocity: pair(velocity)?,
+    })
+  }
+
+  pub fn at(&self, second: u32) -> (i64, i64) {
+    let second = i64::from(second);
+    (
+      self.position.0 + self.velocity.0 * second,
+      self.position.1 + self.velocity.1 * second,
+    )
+  }
+}
+
+/// ## Bounding box of the lights at a second as `(min_x, min_y, max_x, max_y)`
+fn bounds(lights: &[Light], second: u32) -> (i64, i64, i64, i64) {
+  lights.iter().map(|light| light.at(second)).fold(
+    (i64::MAX, i64::MAX, i64::MIN, i64::MIN),
+    |(min_x, min_y, max_x, max_y), (x, y)| (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)),
+  )
+}
+
+fn area(lights: &[Light], second: u32) -> i64 {
+  let (min_x, min_y, max_x, max_y) = bounds(lights, second);
+  (max_x - min_x + 1) * (max_y - min_y + 1)
+}
+
+/// ## Second at which the bounding box of the lights is the smallest
+///
+/// The lights converge until the message appears and then drift apart.
+///
+/// ### Errors
+/// Fails if there are no lights.
+pub fn align(lights: &[Light]) -> Result<u32, AocError> {
+  if lights.is_empty() {
+    return Err(AocError::InvalidInput(
+      "there are no points of light".to_string(),
+    ));
+  }
+
+  let mut second = 0;
+  let mut current = area(lights, second);
+  loop {
+    let next = area(lights, second + 1);
+    if next >= current {
+      return Ok(second);
+    }
+    current = next;
+    second += 1;
+  }
+}
+
+/// ## Draw the lights at a second within their bounding box
+pub fn render(lights: &[Light], second: u32) -> String {
+  let (min_x, min_y, max_x, max_y) = bounds(lights, second);
+  let width = (max_x - min_x + 1) as usize;
+  let height = (max_y - min_y + 1) as usize;
+
+  let mut grid = vec![vec!['.'; width]; height];
+  for (x, y) in lights.iter().map(|light| light.at(second)) {
+    grid[(y - min_y) as usize][(x - min_x) as usize] = '#';
+  }
+
+  grid
+    .into_iter()
+    .map(|row| row.into_iter().collect::<String>() + "\n")
+    .collect()
+}
+
+/// ## Read the letters in a picture drawn by `render`
+///
+/// Letters are separated by empty columns.  Rows are lines of `#` and `.`.
+///
+/// ### Example
+/// ```
+/// let picture = "\
+/// .#....#..#.....
+/// .#....#..#.....
+/// .#....#..#.....
+/// .#....#..#.....
+/// .######..#.....
+/// .#....#..#.....
+/// .#....#..#.....
+/// .#....#..#.....
+/// .#....#..#.....
+/// .#....#..######
+/// ";
+/// assert_eq!( day_10::recognize( picture ).unwrap(), "HL" );
+///
+/// let error = day_10::recognize( "#.#\n.#.\n" ).unwrap_err();
+/// assert_eq!( error.to_string(), "no solution: unknown letter in the message\n#.#\n.#.\n" );
+/// ```
+///
+/// ### Errors
+/// Fails if a letter isn't in the font, the error shows the picture instead.
+pub fn recognize(picture: &str) -> Result<String, AocError> {
+  let rows: Vec<&[u8]> = picture.lines().map(str::as_bytes).collect();
+  let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+  let lit = |x: usize| rows.iter().any(|row| row.get(x) == Some(&b'#'));
+
+  let mut message = String::new();
+  let mut x = 0;
+  while x < width {
+    if !lit(x) {
+      x += 1;
+      continue;
+    }
+    let start = x;
+    while x < width && lit(x) {
+      x += 1;
+    }
+
+    let glyph: Vec<&[u8]> = rows
+      .iter()
+      .map(|row| row.get(start..x).unwrap_or(&[]))
+      .collect();
+    let letter = letters()
+      .find(|(_, font)| {
+        font.len() == glyph.len()
+          && font
+            .iter()
+            .zip(&glyph)
+            .all(|(font, glyph)| font.as_bytes() == *glyph)
+      })
+      .map(|(letter, _)| letter)
+      .ok_or_else(|| AocError::NoSolution(format!("unknown letter in the message\n{}", picture)))?;
+    message.push(letter);
+  }
+  Ok(message)
+}
+
+/// ## Part 1
+/// Read the message that appears in the sky.
+///
+/// ### Example
+/// ```
+/// let input = include_str!( "../examples/stars.txt" );
+/// let lights = day_10::read_lights( input.split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+/// assert_eq!( day_10::part_1( &lights ).unwrap(), "HI" );
+/// ```
+///
+/// ### Errors
+/// Fails if there are no lights or the message can't be read.
+pub fn part_1(lights: &[Light]) -> Result<String, AocError> {
+  recognize(&render(lights, align(lights)?))
+}
+
+/// ## Part 2
+/// Find the second at which the message appears.
+///
+/// ### Example
+/// ```
+/// let input = include_str!( "../examples/stars.txt" );
+/// let lights = day_10::read_lights( input.split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+/// assert_eq!( day_10::part_2( &lights ).unwrap(), 3 );
+/// ```
+///
+/// ### Errors
+/// Fails if there are no lights.
+pub fn part_2(lights: &[Light]) -> Result<u32, AocError> {
+  align(lights)
+}
+
+/// ## Read the points of light from the puzzle input
+pub fn read_lights(input: &str) -> Result<Vec<Light>, AocError> {
+  parse_lines(input, Light::parse)
+}
+
+/// # The Stars Align
+pub struct Day10;
+
+impl Solution for Day10 {
+  const DAY: u8 = 10;
+  const TITLE: &'static str = "The Stars Align";
+
+  type Input<'a> = Vec<Light>;
+  type Output1 = String;
+  type Output2 = u32;
+
+  fn parse(input: &str) -> Result<Vec<Light>, AocError> {
+    read_lights(input)
+  }
+
+  fn part_1(lights: &Vec<Light>) -> Result<String, AocError> {
+    part_1(lights)
+  }
+
+  fn part_2(lights: &Vec<Light>) -> Result<u32, AocError> {
+    part_2(lights)
+  }
+}
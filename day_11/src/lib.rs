@@ -0,0 +1,283 @@
+//! # Day 11 of Advent of Code
+//!
+//! `day_11` contains the solution to the puzzle of
+//! [day 11](https://adventofcode.com/2018/day/11) of the 2018 edition of Advent of Code.
+
+use std::convert::TryFrom;
+use std::fmt;
+use utils::{AocError, Solution, Span};
+
+/// Width and height of the grid of fuel cells in the puzzle
+pub const GRID_SIZE: usize = 300;
+
+/// ## Power level of the fuel cell at `x,y`
+///
+/// Keeps the hundreds digit of the power as written, so a negative power
+/// gives the same digit as its absolute value.
+///
+/// ### Example
+/// ```
+/// assert_eq!( day_11::power_level( 3, 5, 8 ), Some( 4 ) );
+/// assert_eq!( day_11::power_level( 122, 79, 57 ), Some( -5 ) );
+/// assert_eq!( day_11::power_level( 217, 196, 39 ), Some( 0 ) );
+/// assert_eq!( day_11::power_level( 101, 153, 71 ), Some( 4 ) );
+///
+/// // The power is ( 11 * 1 - 1245 ) * 11 = -13574
+/// assert_eq!( day_11::power_level( 1, 1, -1245 ), Some( 0 ) );
+/// assert_eq!( day_11::power_level( 300, 300, i32::MAX ), Some( 0 ) );
+/// assert_eq!( day_11::power_level( usize::MAX, 1, 0 ), None );
+/// ```
+pub fn power_level(x: usize, y: usize, serial: i32) -> Option<i32> {
+  let rack_id = i64::try_from(x).ok()?.checked_add(10)?;
+  let power = rack_id
+    .checked_mul(i64::try_from(y).ok()?)?
+    .checked_add(i64::from(serial))?
+    .checked_mul(rack_id)?;
+  Some((power.unsigned_abs() / 100 % 10) as i32 - 5)
+}
+
+/// ## Square of fuel cells with its total power
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Square {
+  /// 1-based coordinates of the top-left fuel cell
+  pub x: usize,
+  pub y: usize,
+  pub size: usize,
+  pub power: i32,
+}
+
+/// ## Grid of fuel cells as a summed-area table
+///
+/// The table holds the total power of every rectangle from the top-left
+/// corner, so the power of any square takes four lookups.
+pub struct Grid {
+  size: usize,
+  /// Total power of the cells left of and above every corner, row by row
+  sums: Vec<i32>,
+}
+
+impl Grid {
+  /// ### Errors
+  /// Fails if a power level overflows.
+  pub fn new(serial: i32, size: usize) -> Result<Grid, AocError> {
+    let stride = size + 1;
+    let mut sums = vec![0; stride * stride];
+    for y in 1..=size {
+      for x in 1..=size {
+        let level = power_level(x, y, serial).ok_or_else(|| {
+          AocError::InvalidInput(format!(
+            "the power level of {},{} overflows with serial number {}",
+            x, y, serial
+          ))
+        })?;
+        sums[y * stride + x] = level + sums[(y - 1) * stride + x] + sums[y * stride + x - 1]
+          - sums[(y - 1) * stride + x - 1];
+      }
+    }
+    Ok(Grid { size, sums })
+  }
+
+  /// ## Total power of the square with top-left cell `x,y`
+  ///
+  /// ### Example
+  /// ```
+  /// let grid = day_11::Grid::new( 18, 300 ).unwrap();
+  /// assert_eq!( grid.power( 33, 45, 3 ).unwrap(), 29 );
+  ///
+  /// let error = grid.power( 0, 45, 3 ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid input: coordinates start at 1, not 0,45" );
+  /// assert!( grid.power( 298, 45, 4 ).is_err() );
+  /// ```
+  ///
+  /// ### Errors
+  /// Fails if a coordinate is 0 or the square doesn't fit in the grid.
+  pub fn power(&self, x: usize, y: usize, size: usize) -> Result<i32, AocError> {
+    if x == 0 || y == 0 {
+      return Err(AocError::InvalidInput(format!(
+        "coordinates start at 1, not {},{}",
+        x, y
+      )));
+    }
+    let fits = |start: usize| (start - 1).checked_add(size).is_some_and(|end| end <= self.size);
+    if !fits(x) || !fits(y) {
+      return Err(AocError::InvalidInput(format!(
+        "the {0}x{0} square at {1},{2} doesn't fit in the grid",
+        size, x, y
+      )));
+    }
+    Ok(self.sum(x - 1, y - 1, size))
+  }
+
+  /// Total power of the square right of and below the corner `left,top`
+  fn sum(&self, left: usize, top: usize, size: usize) -> i32 {
+    let stride = self.size + 1;
+    let (right, bottom) = (left + size, top + size);
+    self.sums[bottom * stride + right] - self.sums[top * stride + right] - self.sums[bottom * stride + left]
+      + self.sums[top * stride + left]
+  }
+
+  /// ## Square of the given size with the largest total power
+  ///
+  /// ### Example
+  /// ```
+  /// // Power levels of a 5x5 grid with serial number 18
+  /// //   -2  -2  -1  -1  -1
+  /// //   -1   0   0   1   2
+  /// //    0   1   2   3   4
+  /// //    1   2   4  -5  -4
+  /// //    3   4  -5  -3  -2
+  /// let grid = day_11::Grid::new( 18, 5 ).unwrap();
+  /// assert_eq!( grid.power( 1, 1, 5 ).unwrap(), 0 );
+  ///
+  /// let best = grid.best_square( 3 ).unwrap();
+  /// assert_eq!( ( best.x, best.y, best.power ), ( 1, 3, 12 ) );
+  ///
+  /// // Both 4,2 and 1,4 total 10, the first in reading order wins
+  /// let best = grid.best_square( 2 ).unwrap();
+  /// assert_eq!( ( best.x, best.y, best.power ), ( 4, 2, 10 ) );
+  ///
+  /// assert!( grid.best_square( 6 ).is_none() );
+  /// ```
+  pub fn best_square(&self, size: usize) -> Option<Square> {
+    if size == 0 || size > self.size {
+      return None;
+    }
+
+    let corners = self.size - size + 1;
+    (1..=corners)
+      .flat_map(|y| (1..=corners).map(move |x| (x, y)))
+      .map(|(x, y)| Square {
+        x,
+        y,
+        size,
+        power: self.sum(x - 1, y - 1, size),
+      })
+      .fold(None, |best: Option<Square>, square| match best {
+        Some(best) if best.power >= square.power => Some(best),
+        _ => Some(square),
+      })
+  }
+
+  /// ## Square of any size with the largest total power
+  ///
+  /// ### Example
+  /// ```
+  /// // The 5x5 grid of `best_square`, no square beats the 3x3 one at 1,3
+  /// let best = day_11::Grid::new( 18, 5 ).unwrap().best_any().unwrap();
+  /// assert_eq!( ( best.x, best.y, best.size, best.power ), ( 1, 3, 3, 12 ) );
+  /// ```
+  pub fn best_any(&self) -> Option<Square> {
+    (1..=self.size)
+      .filter_map(|size| self.best_square(size))
+      .fold(None, |best: Option<Square>, square| match best {
+        Some(best) if best.power >= square.power => Some(best),
+        _ => Some(square),
+      })
+  }
+}
+
+/// ## Top-left coordinate of a square, as the puzzle wants it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Corner(pub usize, pub usize);
+
+impl fmt::Display for Corner {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{},{}", self.0, self.1)
+  }
+}
+
+/// ## Top-left coordinate and size of a square, as the puzzle wants it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Identifier(pub usize, pub usize, pub usize);
+
+impl fmt::Display for Identifier {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{},{},{}", self.0, self.1, self.2)
+  }
+}
+
+fn too_small() -> AocError {
+  AocError::InvalidInput("the grid is too small".to_string())
+}
+
+/// ## Part 1
+/// Find the 3x3 square with the largest total power.
+///
+/// ### Example
+/// ```
+/// use day_11::Corner;
+///
+/// assert_eq!( day_11::part_1( 18, 300 ).unwrap(), Corner( 33, 45 ) );
+/// assert_eq!( day_11::part_1( 42, 300 ).unwrap(), Corner( 21, 61 ) );
+/// ```
+///
+/// ### Errors
+/// Fails if the grid is smaller than 3x3 or a power level overflows.
+pub fn part_1(serial: i32, grid_size: usize) -> Result<Corner, AocError> {
+  let square = Grid::new(serial, grid_size)?.best_square(3).ok_or_else(too_small)?;
+  Ok(Corner(square.x, square.y))
+}
+
+/// ## Part 2
+/// Find the square of any size with the largest total power.
+///
+/// ### Example
+/// ```
+/// use day_11::Identifier;
+///
+/// assert_eq!( day_11::part_2( 18, 300 ).unwrap(), Identifier( 90, 269, 16 ) );
+/// assert_eq!( day_11::part_2( 42, 300 ).unwrap(), Identifier( 232, 251, 12 ) );
+/// ```
+///
+/// ### Errors
+/// Fails if the grid is empty or a power level overflows.
+pub fn part_2(serial: i32, grid_size: usize) -> Result<Identifier, AocError> {
+  let square = Grid::new(serial, grid_size)?.best_any().ok_or_else(too_small)?;
+  Ok(Identifier(square.x, square.y, square.size))
+}
+
+/// ## Read the grid serial number from the puzzle input
+///
+/// ### Example
+/// ```
+/// assert_eq!( day_11::serial( "18\n" ).unwrap(), 18 );
+///
+/// let error = day_11::serial( "eighteen\n" ).unwrap_err();
+/// assert_eq!( error.to_string(), "invalid grid serial number \"eighteen\" (bytes 0..8)" );
+///
+/// // Serial numbers are 32 bits
+/// assert!( day_11::serial( "99999999999\n" ).is_err() );
+/// ```
+pub fn serial(input: &str) -> Result<i32, AocError> {
+  let serial = input.trim();
+  serial.parse().map_err(|_| {
+    AocError::parse_at(
+      Span::of(input, serial),
+      format!("invalid grid serial number \"{}\"", serial),
+    )
+  })
+}
+
+/// # Chronal Charge
+pub struct Day11;
+
+impl Solution for Day11 {
+  const DAY: u8 = 11;
+  const TITLE: &'static str = "Chronal Charge";
+
+  type Input<'a> = i32;
+  type Output1 = Corner;
+  type Output2 = Identifier;
+
+  fn parse(input: &str) -> Result<i32, AocError> {
+    serial(input)
+  }
+
+  fn part_1(serial: &i32) -> Result<Corner, AocError> {
+    part_1(*serial, GRID_SIZE)
+  }
+
+  fn part_2(serial: &i32) -> Result<Identifier, AocError> {
+    part_2(*serial, GRID_SIZE)
+  }
+}
@@ -0,0 +1,300 @@
+//! # Day 12 of Advent of Code
+//!
+//! `day_12` contains the solution to the puzzle of
+//! [day 12](https://adventofcode.com/2018/day/12) of the 2018 edition of Advent of Code.
+
+use std::collections::HashMap;
+use std::fmt;
+use utils::{AocError, Solution, Span};
+
+/// Generations simulated while looking for the plants to repeat
+pub const MAX_GENERATIONS: u64 = 1_000;
+
+/// ## A row of pots, from the first to the last pot with a plant
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pots {
+  /// Number of the first pot
+  offset: i64,
+  plants: Vec<bool>,
+}
+
+impl Pots {
+  /// ## Pots with plants from pot 0 onwards
+  pub fn new(plants: Vec<bool>) -> Pots {
+    Pots { offset: 0, plants }.trimmed()
+  }
+
+  fn trimmed(mut self) -> Pots {
+    match self.plants.iter().position(|&plant| plant) {
+      Some(first) => {
+        let last = self.plants.iter().rposition(|&plant| plant).unwrap_or(first);
+        self.plants.truncate(last + 1);
+        self.plants.drain(..first);
+        self.offset += first as i64;
+      }
+      None => {
+        self.plants.clear();
+        self.offset = 0;
+      }
+    }
+    self
+  }
+
+  fn has_plant(&self, pot: i64) -> bool {
+    let index = pot - self.offset;
+    index >= 0 && self.plants.get(index as usize) == Some(&true)
+  }
+
+  /// ## The pots in the next generation
+  pub fn next(&self, rules: &Rules) -> Pots {
+    let start = self.offset - 2;
+    let end = self.offset + self.plants.len() as i64 + 2;
+    let plants = (start..end)
+      .map(|pot| {
+        let pattern = (pot - 2..=pot + 2).fold(0, |pattern, pot| pattern << 1 | self.has_plant(pot) as usize);
+        rules.0[pattern]
+      })
+      .collect();
+
+    Pots {
+      offset: start,
+      plants,
+    }
+    .trimmed()
+  }
+
+  /// ## Sum of the numbers of the pots with a plant
+  pub fn sum(&self) -> i64 {
+    (self.offset..)
+      .zip(&self.plants)
+      .filter(|(_, &plant)| plant)
+      .map(|(pot, _)| pot)
+      .sum()
+  }
+
+  pub fn count(&self) -> usize {
+    self.plants.iter().filter(|&&plant| plant).count()
+  }
+}
+
+impl fmt::Display for Pots {
+  /// The pots from the first to the last plant, as in the puzzle
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let row: String = self.plants.iter().map(|&plant| if plant { '#' } else { '.' }).collect();
+    write!(f, "{}", row)
+  }
+}
+
+/// ## Whether a pot has a plant next generation, for every pattern of five pots
+///
+/// Patterns are indexed by the pots as bits, the leftmost pot being the most
+/// significant.  Patterns without a rule produce no plant.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rules(pub [bool; 32]);
+
+/// ## Initial state and rules of the puzzle
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Garden {
+  pub initial: Pots,
+  pub rules: Rules,
+}
+
+fn plants(field: &str, input: &str) -> Result<Vec<bool>, AocError> {
+  field
+    .char_indices()
+    .map(|(index, pot)| match pot {
+      '#' => Ok(true),
+      '.' => Ok(false),
+      _ => {
+        let start = Span::of(input, field).start + index;
+        Err(AocError::parse_at(
+          Span::new(start, start + pot.len_utf8()),
+          format!("invalid pot {:?}", pot),
+        ))
+      }
+    })
+    .collect()
+}
+
+impl Garden {
+  /// ## Parse the puzzle input
+  ///
+  /// ### Example
+  /// ```
+  /// let garden = day_12::Garden::parse( "initial state: #..#\n\n...## => #\n..#.. => .\n" ).unwrap();
+  /// assert_eq!( garden.initial.to_string(), "#..#" );
+  /// assert!( garden.rules.0[0b00011] );
+  /// assert!( !garden.rules.0[0b00100] );
+  ///
+  /// let error = day_12::Garden::parse( "initial state: #..#\n\n...## => #\n..#.. => o\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid pot 'o' (bytes 41..42)" );
+  ///
+  /// let error = day_12::Garden::parse( "initial state: #..#\n\n...# => #\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "a rule needs five pots (bytes 21..25)" );
+  ///
+  /// let error = day_12::Garden::parse( "initial state: #..#\n\n..... => #\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "a rule can't grow a plant among empty pots (bytes 21..31)" );
+  /// ```
+  ///
+  /// ### Errors
+  /// Fails on a malformed line, or a rule that grows a plant among five empty
+  /// pots.
+  pub fn parse(input: &str) -> Result<Garden, AocError> {
+    let mut lines = input.lines();
+    let first = lines.next().unwrap_or("");
+    let initial = first
+      .strip_prefix("initial state: ")
+      .ok_or_else(|| AocError::parse_at(Span::of(input, first), "expected the initial state"))?;
+    let initial = Pots::new(plants(initial, input)?);
+
+    let mut rules = [false; 32];
+    for line in lines.filter(|line| !line.trim().is_empty()) {
+      let (pattern, result) = line
+        .split_once(" => ")
+        .ok_or_else(|| AocError::parse_at(Span::of(input, line), format!("invalid rule \"{}\"", line)))?;
+      if pattern.len() != 5 {
+        return Err(AocError::parse_at(Span::of(input, pattern), "a rule needs five pots"));
+      }
+
+      let pattern = plants(pattern, input)?
+        .into_iter()
+        .fold(0, |pattern, plant| pattern << 1 | plant as usize);
+      match plants(result, input)?.as_slice() {
+        // Every empty pot of the infinite row would get a plant
+        [true] if pattern == 0 => {
+          return Err(AocError::parse_at(
+            Span::of(input, line),
+            "a rule can't grow a plant among empty pots",
+          ))
+        }
+        [plant] => rules[pattern] = *plant,
+        _ => return Err(AocError::parse_at(Span::of(input, result), "a rule produces one pot")),
+      }
+    }
+
+    Ok(Garden {
+      initial,
+      rules: Rules(rules),
+    })
+  }
+}
+
+/// ## Sum of the numbers of the pots with a plant after some generations
+///
+/// Eventually the plants repeat an earlier generation, possibly shifted.
+/// From then on every generation is a shifted copy of one in the cycle, and
+/// the remaining generations are skipped.
+///
+/// ### Example
+/// ```
+/// let garden = day_12::Garden::parse( "initial state: #\n\n..#.. => .\n.#... => #\n" ).unwrap();
+/// // A single plant moving right forever
+/// assert_eq!( day_12::sum_after( &garden, 5 ).unwrap(), 5 );
+/// assert_eq!( day_12::sum_after( &garden, 50_000_000_000 ).unwrap(), 50_000_000_000 );
+///
+/// // A plant that grows a neighbour and then loses its left one, every two generations
+/// let garden = day_12::Garden::parse( "initial state: #\n\n..#.. => #\n.#... => #\n.##.. => #\n" ).unwrap();
+/// assert_eq!( day_12::sum_after( &garden, 4 ).unwrap(), 2 );
+/// assert_eq!( day_12::sum_after( &garden, 5 ).unwrap(), 5 );
+/// assert_eq!( day_12::sum_after( &garden, 50_000_000_000 ).unwrap(), 25_000_000_000 );
+///
+/// // A pot gets a plant when exactly one of its neighbours has one, the
+/// // plants keep spreading and never repeat
+/// let mut rules = String::new();
+/// for left in [ '.', '#' ] {
+///   for center in [ '.', '#' ] {
+///     for right in [ '.', '#' ] {
+///       rules += &format!( "{}#{}.{} => #\n{}.{}#{} => #\n", left, center, right, left, center, right );
+///     }
+///   }
+/// }
+/// let garden = day_12::Garden::parse( &format!( "initial state: #\n\n{}", rules ) ).unwrap();
+/// assert!( day_12::sum_after( &garden, 50_000_000_000 ).is_err() );
+/// ```
+///
+/// ### Errors
+/// Fails if the plants don't repeat within `MAX_GENERATIONS` generations.
+pub fn sum_after(garden: &Garden, generations: u64) -> Result<i64, AocError> {
+  // Generation in which every pattern of plants first appeared
+  let mut seen: HashMap<Vec<bool>, u64> = HashMap::new();
+  // Offset, sum and count of every generation
+  let mut history: Vec<(i64, i64, i64)> = Vec::new();
+
+  let mut pots = garden.initial.clone();
+  for generation in 0..generations {
+    if let Some(&start) = seen.get(&pots.plants) {
+      let period = generation - start;
+      let shift = pots.offset - history[start as usize].0;
+      let (cycles, rest) = ((generations - start) / period, (generations - start) % period);
+      let (_, sum, count) = history[(start + rest) as usize];
+      return Ok(sum + cycles as i64 * shift * count);
+    }
+    if generation == MAX_GENERATIONS {
+      return Err(AocError::NoSolution(format!(
+        "the plants don't repeat within {} generations",
+        MAX_GENERATIONS
+      )));
+    }
+
+    history.push((pots.offset, pots.sum(), pots.count() as i64));
+    let next = pots.next(&garden.rules);
+    seen.insert(pots.plants, generation);
+    pots = next;
+  }
+  Ok(pots.sum())
+}
+
+/// ## Part 1
+/// Sum the numbers of the pots with a plant after 20 generations.
+///
+/// ### Example
+/// ```
+/// let garden = day_12::Garden::parse( include_str!( "../examples/pots.txt" ).split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+/// assert_eq!( day_12::part_1( &garden ).unwrap(), 325 );
+/// ```
+///
+/// ### Errors
+/// Doesn't fail, 20 generations are well within `MAX_GENERATIONS` so
+/// `sum_after` never gives up.
+pub fn part_1(garden: &Garden) -> Result<i64, AocError> {
+  sum_after(garden, 20)
+}
+
+/// ## Part 2
+/// Sum the numbers of the pots with a plant after fifty billion generations.
+///
+/// ### Example
+/// ```
+/// let garden = day_12::Garden::parse( include_str!( "../examples/pots.txt" ).split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+/// assert_eq!( day_12::part_2( &garden ).unwrap(), 999999999374 );
+/// ```
+///
+/// ### Errors
+/// Fails if the plants don't repeat, see `sum_after`.
+pub fn part_2(garden: &Garden) -> Result<i64, AocError> {
+  sum_after(garden, 50_000_000_000)
+}
+
+/// # Subterranean Sustainability
+pub struct Day12;
+
+impl Solution for Day12 {
+  const DAY: u8 = 12;
+  const TITLE: &'static str = "Subterranean Sustainability";
+
+  type Input<'a> = Garden;
+  type Output1 = i64;
+  type Output2 = i64;
+
+  fn parse(input: &str) -> Result<Garden, AocError> {
+    Garden::parse(input)
+  }
+
+  fn part_1(garden: &Garden) -> Result<i64, AocError> {
+    part_1(garden)
+  }
+
+  fn part_2(garden: &Garden) -> Result<i64, AocError> {
+    part_2(garden)
+  }
+}
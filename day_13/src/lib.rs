@@ -0,0 +1,374 @@
+//! # Day 13 of Advent of Code
+//!
+//! `day_13` contains the solution to the puzzle of
+//! [day 13](https://adventofcode.com/2018/day/13) of the 2018 edition of Advent of Code.
+
+use std::fmt;
+use utils::{AocError, Solution, Span};
+
+/// Ticks after which carts that never crash are given up on
+pub const MAX_TICKS: usize = 1_000_000;
+
+/// ## A location on the map, as the puzzle wants it
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub struct Location {
+  pub x: usize,
+  pub y: usize,
+}
+
+impl fmt::Display for Location {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{},{}", self.x, self.y)
+  }
+}
+
+/// ## A cart with the direction it is heading in
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cart {
+  pub location: Location,
+  /// Step per tick, `y` grows downwards
+  pub direction: (isize, isize),
+  /// Intersections passed, the cart turns left, goes straight and turns right in turn
+  pub intersections: usize,
+  pub crashed: bool,
+}
+
+impl Cart {
+  fn symbol(&self) -> char {
+    match self.direction {
+      (0, -1) => '^',
+      (0, 1) => 'v',
+      (-1, 0) => '<',
+      _ => '>',
+    }
+  }
+
+  /// Move one step along the track
+  fn advance(&mut self, tracks: &[Vec<u8>]) -> Result<(), AocError> {
+    let (dx, dy) = self.direction;
+    let next = self
+      .location
+      .x
+      .checked_add_signed(dx)
+      .zip(self.location.y.checked_add_signed(dy));
+    let piece = next.and_then(|(x, y)| tracks.get(y)?.get(x).copied());
+
+    self.direction = match piece {
+      Some(b'/') => (-dy, -dx),
+      Some(b'\\') => (dy, dx),
+      Some(b'+') => {
+        self.intersections += 1;
+        match self.intersections % 3 {
+          1 => (dy, -dx),
+          2 => (dx, dy),
+          _ => (-dy, dx),
+        }
+      }
+      Some(b'-') if dy == 0 => (dx, dy),
+      Some(b'|') if dx == 0 => (dx, dy),
+      _ => {
+        return Err(AocError::InvalidInput(format!(
+          "the cart at {} runs off the track",
+          self.location
+        )))
+      }
+    };
+    if let Some((x, y)) = next {
+      self.location = Location { x, y };
+    }
+    Ok(())
+  }
+}
+
+/// ## The tracks with the carts riding them
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Mine {
+  /// Tracks row by row, without the carts
+  tracks: Vec<Vec<u8>>,
+  pub carts: Vec<Cart>,
+  /// Locations of the crashes during the last tick
+  pub crashes: Vec<Location>,
+  pub ticks: usize,
+}
+
+impl Mine {
+  /// ## Parse the map of the puzzle input
+  ///
+  /// ### Example
+  /// ```
+  /// let mine = day_13::Mine::parse( "/>-\\\n\\--/\n" ).unwrap();
+  /// assert_eq!( mine.carts.len(), 1 );
+  /// assert_eq!( mine.to_string(), "/>-\\\n\\--/\n" );
+  ///
+  /// let error = day_13::Mine::parse( "/>-\\\n\\-*/\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid track '*' (bytes 7..8)" );
+  ///
+  /// let error = day_13::Mine::parse( "<-\\\n\\-/\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "cart runs off the track (bytes 0..1)" );
+  /// ```
+  ///
+  /// ### Errors
+  /// Fails on an unknown character or a cart that doesn't sit on a track.
+  pub fn parse(input: &str) -> Result<Mine, AocError> {
+    let mut tracks = Vec::new();
+    let mut carts = Vec::new();
+
+    for (y, line) in input.lines().enumerate() {
+      let mut row = line.as_bytes().to_vec();
+      for (x, piece) in row.iter_mut().enumerate() {
+        let span = || {
+          let start = Span::of(input, line).start + x;
+          Span::new(start, start + 1)
+        };
+        let (track, direction) = match *piece {
+          b'^' => (b'|', (0, -1)),
+          b'v' => (b'|', (0, 1)),
+          b'<' => (b'-', (-1, 0)),
+          b'>' => (b'-', (1, 0)),
+          b'/' | b'\\' | b'+' | b'-' | b'|' | b' ' => continue,
+          other => {
+            return Err(AocError::parse_at(
+              span(),
+              format!("invalid track {:?}", char::from(other)),
+            ))
+          }
+        };
+        *piece = track;
+        carts.push((
+          span(),
+          Cart {
+            location: Location { x, y },
+            direction,
+            intersections: 0,
+            crashed: false,
+          },
+        ));
+      }
+      tracks.push(row);
+    }
+
+    let mine = Mine {
+      tracks,
+      carts: Vec::new(),
+      crashes: Vec::new(),
+      ticks: 0,
+    };
+    let carts = carts
+      .into_iter()
+      .map(|(span, cart)| {
+        let (dx, dy) = cart.direction;
+        let next = (cart.location.x as isize + dx, cart.location.y as isize + dy);
+        if mine.piece(next.0, next.1) == b' ' {
+          Err(AocError::parse_at(span, "cart runs off the track"))
+        } else {
+          Ok(cart)
+        }
+      })
+      .collect::<Result<_, _>>()?;
+
+    Ok(Mine { carts, ..mine })
+  }
+
+  /// Track at a location, a space off the map
+  fn piece(&self, x: isize, y: isize) -> u8 {
+    if x < 0 || y < 0 {
+      return b' ';
+    }
+    self
+      .tracks
+      .get(y as usize)
+      .and_then(|row| row.get(x as usize))
+      .copied()
+      .unwrap_or(b' ')
+  }
+
+  /// ## Carts that haven't crashed
+  pub fn remaining(&self) -> impl Iterator<Item = &Cart> {
+    self.carts.iter().filter(|cart| !cart.crashed)
+  }
+
+  /// ## Move every cart one step, top to bottom and left to right
+  ///
+  /// Carts that crash are removed immediately.  Returns the locations of the
+  /// crashes during this tick, in the order they happen.
+  ///
+  /// ### Example
+  /// ```
+  /// let mut mine = day_13::Mine::parse( "|\nv\n|\n|\n^\n|\n" ).unwrap();
+  /// assert!( mine.tick().unwrap().is_empty() );
+  /// assert_eq!( mine.tick().unwrap(), vec![ day_13::Location { x: 0, y: 3 } ] );
+  /// assert_eq!( mine.to_string(), "|\n|\n|\nX\n|\n|\n" );
+  ///
+  /// // The track ends after the first step
+  /// let mut mine = day_13::Mine::parse( ">-\n" ).unwrap();
+  /// assert!( mine.tick().is_ok() );
+  /// let error = mine.tick().unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid input: the cart at 1,0 runs off the track" );
+  /// ```
+  ///
+  /// ### Errors
+  /// Fails if a cart leaves the track.
+  pub fn tick(&mut self) -> Result<Vec<Location>, AocError> {
+    self.carts.retain(|cart| !cart.crashed);
+    self.carts.sort_by_key(|cart| (cart.location.y, cart.location.x));
+    self.crashes.clear();
+
+    for index in 0..self.carts.len() {
+      if self.carts[index].crashed {
+        continue;
+      }
+      self.carts[index].advance(&self.tracks)?;
+
+      let location = self.carts[index].location;
+      let others: Vec<usize> = (0..self.carts.len())
+        .filter(|&other| other != index && !self.carts[other].crashed)
+        .filter(|&other| self.carts[other].location == location)
+        .collect();
+      if !others.is_empty() {
+        self.carts[index].crashed = true;
+        others.into_iter().for_each(|other| self.carts[other].crashed = true);
+        self.crashes.push(location);
+      }
+    }
+
+    self.ticks += 1;
+    Ok(self.crashes.clone())
+  }
+
+  /// ## The map before the first tick and after every tick
+  ///
+  /// Stops once at most one cart is left, or after a cart leaves the track.
+  ///
+  /// ### Example
+  /// ```
+  /// let mine = day_13::Mine::parse( include_str!( "../examples/crash.txt" ).split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+  /// let frames: Vec<String> = mine.frames().collect::<Result<_, _>>().unwrap();
+  /// assert_eq!( frames.len(), 15 );
+  ///
+  /// let last: Vec<&str> = frames[14].lines().map( str::trim_end ).collect();
+  /// assert_eq!( last, vec![
+  ///   "/---\\",
+  ///   "|   |  /----\\",
+  ///   "| /-+--+-\\  |",
+  ///   "| | |  X |  |",
+  ///   "\\-+-/  \\-+--/",
+  ///   "  \\------/",
+  /// ] );
+  /// ```
+  pub fn frames(&self) -> impl Iterator<Item = Result<String, AocError>> {
+    let mut mine = self.clone();
+    let mut first = true;
+    let mut failed = false;
+    std::iter::from_fn(move || {
+      if first {
+        first = false;
+      } else if failed || mine.remaining().count() < 2 || mine.ticks >= MAX_TICKS {
+        return None;
+      } else if let Err(err) = mine.tick() {
+        failed = true;
+        return Some(Err(err));
+      }
+      Some(Ok(mine.to_string()))
+    })
+  }
+}
+
+impl fmt::Display for Mine {
+  /// The map as in the puzzle, with the carts and the crashes of the last tick
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut map: Vec<Vec<char>> = self
+      .tracks
+      .iter()
+      .map(|row| row.iter().map(|&piece| char::from(piece)).collect())
+      .collect();
+    for cart in self.remaining() {
+      map[cart.location.y][cart.location.x] = cart.symbol();
+    }
+    for crash in &self.crashes {
+      map[crash.y][crash.x] = 'X';
+    }
+
+    for row in map {
+      writeln!(f, "{}", row.into_iter().collect::<String>())?;
+    }
+    Ok(())
+  }
+}
+
+fn endless() -> AocError {
+  AocError::NoSolution(format!("the carts keep riding after {} ticks", MAX_TICKS))
+}
+
+/// ## Part 1
+/// Find the location of the first crash.
+///
+/// ### Example
+/// ```
+/// let mine = day_13::Mine::parse( include_str!( "../examples/crash.txt" ).split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+/// assert_eq!( day_13::part_1( &mine ).unwrap().to_string(), "7,3" );
+/// ```
+///
+/// ### Errors
+/// Fails if the carts don't crash or a cart leaves the track.
+pub fn part_1(mine: &Mine) -> Result<Location, AocError> {
+  let mut mine = mine.clone();
+  while mine.ticks < MAX_TICKS {
+    if mine.remaining().count() < 2 {
+      return Err(AocError::NoSolution("there are too few carts to crash".to_string()));
+    }
+    if let Some(&crash) = mine.tick()?.first() {
+      return Ok(crash);
+    }
+  }
+  Err(endless())
+}
+
+/// ## Part 2
+/// Find the location of the last cart once all others have crashed.
+///
+/// ### Example
+/// ```
+/// let mine = day_13::Mine::parse( include_str!( "../examples/last_cart.txt" ).split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+/// assert_eq!( day_13::part_2( &mine ).unwrap().to_string(), "6,4" );
+/// ```
+///
+/// ### Errors
+/// Fails if no cart or several carts remain, or a cart leaves the track.
+pub fn part_2(mine: &Mine) -> Result<Location, AocError> {
+  let mut mine = mine.clone();
+  while mine.ticks < MAX_TICKS {
+    let remaining: Vec<&Cart> = mine.remaining().collect();
+    match remaining.as_slice() {
+      [] => return Err(AocError::NoSolution("every cart crashed".to_string())),
+      [last] if mine.ticks > 0 => return Ok(last.location),
+      _ => {
+        mine.tick()?;
+      }
+    }
+  }
+  Err(endless())
+}
+
+/// # Mine Cart Madness
+pub struct Day13;
+
+impl Solution for Day13 {
+  const DAY: u8 = 13;
+  const TITLE: &'static str = "Mine Cart Madness";
+
+  type Input<'a> = Mine;
+  type Output1 = Location;
+  type Output2 = Location;
+
+  fn parse(input: &str) -> Result<Mine, AocError> {
+    Mine::parse(input)
+  }
+
+  fn part_1(mine: &Mine) -> Result<Location, AocError> {
+    part_1(mine)
+  }
+
+  fn part_2(mine: &Mine) -> Result<Location, AocError> {
+    part_2(mine)
+  }
+}
@@ -0,0 +1,210 @@
+//! # Day 14 of Advent of Code
+//!
+//! `day_14` contains the solution to the puzzle of
+//! [day 14](https://adventofcode.com/2018/day/14) of the 2018 edition of Advent of Code.
+
+use utils::{AocError, Solution, Span};
+
+/// Most recipes made, for the scores after a number of recipes or while
+/// looking for a sequence of scores
+pub const MAX_RECIPES: usize = 100_000_000;
+
+/// ## The scoreboard of recipes with the current recipe of both elves
+///
+/// Every score is a single digit and takes a single byte.
+///
+/// ### Example
+/// ```
+/// let mut scoreboard = day_14::Scoreboard::new();
+/// assert_eq!( scoreboard.step(), 2 );
+/// assert_eq!( scoreboard.scores(), &[ 3, 7, 1, 0 ] );
+/// assert_eq!( scoreboard.step(), 2 );
+/// assert_eq!( scoreboard.step(), 1 );
+/// assert_eq!( scoreboard.scores(), &[ 3, 7, 1, 0, 1, 0, 1 ] );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scoreboard {
+  scores: Vec<u8>,
+  elves: [usize; 2],
+}
+
+impl Default for Scoreboard {
+  fn default() -> Scoreboard {
+    Scoreboard::new()
+  }
+}
+
+impl Scoreboard {
+  pub fn new() -> Scoreboard {
+    Scoreboard {
+      scores: vec![3, 7],
+      elves: [0, 1],
+    }
+  }
+
+  pub fn scores(&self) -> &[u8] {
+    &self.scores
+  }
+
+  /// ## Combine the current recipes and move the elves
+  ///
+  /// Returns the number of new recipes, one or two.
+  pub fn step(&mut self) -> usize {
+    let [first, second] = self.elves;
+    let sum = self.scores[first] + self.scores[second];
+    let added = if sum >= 10 {
+      self.scores.extend_from_slice(&[sum / 10, sum % 10]);
+      2
+    } else {
+      self.scores.push(sum);
+      1
+    };
+
+    let len = self.scores.len();
+    for elf in self.elves.iter_mut() {
+      *elf = (*elf + 1 + self.scores[*elf] as usize) % len;
+    }
+    added
+  }
+}
+
+/// ## Part 1
+/// Find the scores of the ten recipes after the first `recipes`.
+///
+/// ### Example
+/// ```
+/// assert_eq!( day_14::part_1( 9 ).unwrap(), "5158916779" );
+/// assert_eq!( day_14::part_1( 5 ).unwrap(), "0124515891" );
+/// assert_eq!( day_14::part_1( 18 ).unwrap(), "9251071085" );
+/// assert_eq!( day_14::part_1( 2018 ).unwrap(), "5941429882" );
+///
+/// let error = day_14::part_1( usize::MAX ).unwrap_err();
+/// assert_eq!( error.to_string(), format!( "invalid input: more than {} recipes", day_14::MAX_RECIPES ) );
+/// ```
+///
+/// ### Errors
+/// Fails if `recipes` is above `MAX_RECIPES`.
+pub fn part_1(recipes: usize) -> Result<String, AocError> {
+  if recipes > MAX_RECIPES {
+    return Err(AocError::InvalidInput(format!("more than {} recipes", MAX_RECIPES)));
+  }
+
+  let mut scoreboard = Scoreboard::new();
+  while scoreboard.scores.len() < recipes + 10 {
+    scoreboard.step();
+  }
+
+  Ok(
+    scoreboard.scores[recipes..recipes + 10]
+      .iter()
+      .map(|&score| char::from(b'0' + score))
+      .collect(),
+  )
+}
+
+/// ## Part 2
+/// Count the recipes left of the first occurrence of a sequence of scores,
+/// among the first `max_recipes` recipes.
+///
+/// A step can add two recipes, so the sequence can end at the last or at the
+/// one but last recipe.
+///
+/// ### Example
+/// ```
+/// assert_eq!( day_14::part_2( &[ 5, 1, 5, 8, 9 ], 100 ).unwrap(), 9 );
+/// assert_eq!( day_14::part_2( &[ 0, 1, 2, 4, 5 ], 100 ).unwrap(), 5 );
+/// assert_eq!( day_14::part_2( &[ 9, 2, 5, 1, 0 ], 100 ).unwrap(), 18 );
+/// assert_eq!( day_14::part_2( &[ 5, 9, 4, 1, 4 ], 10_000 ).unwrap(), 2018 );
+///
+/// // The first step adds "1, 0", this ends one before the last recipe
+/// assert_eq!( day_14::part_2( &[ 3, 7, 1 ], 100 ).unwrap(), 0 );
+/// assert_eq!( day_14::part_2( &[ 1, 0, 1 ], 100 ).unwrap(), 2 );
+///
+/// // "5, 1, 5, 8, 9" ends at the 14th recipe
+/// assert!( day_14::part_2( &[ 5, 1, 5, 8, 9 ], 13 ).is_err() );
+/// assert!( day_14::part_2( &[ 5, 1, 5, 8, 9 ], 14 ).is_ok() );
+/// ```
+///
+/// ### Errors
+/// Fails if the sequence isn't among the first `max_recipes` recipes.
+pub fn part_2(sequence: &[u8], max_recipes: usize) -> Result<usize, AocError> {
+  if sequence.is_empty() {
+    return Ok(0);
+  }
+
+  let mut scoreboard = Scoreboard::new();
+  // Every end position up to here has been checked
+  let mut checked = sequence.len() - 1;
+  while checked < max_recipes {
+    let scores = scoreboard.scores();
+    let last = scores.len().min(max_recipes);
+    if let Some(end) = (checked + 1..=last).find(|&end| &scores[end - sequence.len()..end] == sequence) {
+      return Ok(end - sequence.len());
+    }
+    checked = checked.max(last);
+    scoreboard.step();
+  }
+  Err(AocError::NoSolution(format!(
+    "the scores don't appear within {} recipes",
+    max_recipes
+  )))
+}
+
+/// ## Read the puzzle input as digits
+///
+/// ### Example
+/// ```
+/// assert_eq!( day_14::digits( "01245\n" ).unwrap(), vec![ 0, 1, 2, 4, 5 ] );
+///
+/// let error = day_14::digits( "0124x\n" ).unwrap_err();
+/// assert_eq!( error.to_string(), "invalid digit 'x' (bytes 4..5)" );
+///
+/// assert!( day_14::digits( "\n" ).is_err() );
+/// ```
+pub fn digits(input: &str) -> Result<Vec<u8>, AocError> {
+  let digits = input.trim();
+  if digits.is_empty() {
+    return Err(AocError::parse("expected a number"));
+  }
+
+  let start = Span::of(input, digits).start;
+  digits
+    .char_indices()
+    .map(|(index, digit)| {
+      digit.to_digit(10).map(|digit| digit as u8).ok_or_else(|| {
+        AocError::parse_at(
+          Span::new(start + index, start + index + digit.len_utf8()),
+          format!("invalid digit {:?}", digit),
+        )
+      })
+    })
+    .collect()
+}
+
+/// # Chocolate Charts
+pub struct Day14;
+
+impl Solution for Day14 {
+  const DAY: u8 = 14;
+  const TITLE: &'static str = "Chocolate Charts";
+
+  /// The input is a number for part 1 and a sequence of scores for part 2
+  type Input<'a> = Vec<u8>;
+  type Output1 = String;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Vec<u8>, AocError> {
+    digits(input)
+  }
+
+  fn part_1(digits: &Vec<u8>) -> Result<String, AocError> {
+    let recipes = digits.iter().try_fold(0usize, |number, &digit| {
+      number.checked_mul(10)?.checked_add(usize::from(digit))
+    });
+    part_1(recipes.unwrap_or(usize::MAX))
+  }
+
+  fn part_2(digits: &Vec<u8>) -> Result<usize, AocError> {
+    part_2(digits, MAX_RECIPES)
+  }
+}
@@ -0,0 +1,417 @@
+//! # Day 15 of Advent of Code
+//!
+//! `day_15` contains the solution to the puzzle of
+//! [day 15](https://adventofcode.com/2018/day/15) of the 2018 edition of Advent of Code.
+
+use std::collections::VecDeque;
+use std::fmt;
+use utils::{AocError, Solution, Span};
+
+/// Hit points every unit starts with
+pub const HIT_POINTS: i32 = 200;
+/// Attack power of goblins, and of elves in part 1
+pub const ATTACK_POWER: i32 = 3;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Race {
+  Elf,
+  Goblin,
+}
+
+impl Race {
+  fn symbol(self) -> char {
+    match self {
+      Race::Elf => 'E',
+      Race::Goblin => 'G',
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Unit {
+  pub race: Race,
+  /// Position as `(y, x)`, so positions sort in reading order
+  pub position: (usize, usize),
+  pub hit_points: i32,
+  pub attack_power: i32,
+}
+
+impl Unit {
+  pub fn is_alive(&self) -> bool {
+    self.hit_points > 0
+  }
+}
+
+/// Squares next to a position, in reading order
+fn neighbours((y, x): (usize, usize)) -> [(usize, usize); 4] {
+  [(y - 1, x), (y, x - 1), (y, x + 1), (y + 1, x)]
+}
+
+/// ## Result of a combat
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Outcome {
+  /// Full rounds completed
+  pub rounds: usize,
+  /// Hit points left of the winning units
+  pub hit_points: i32,
+}
+
+impl Outcome {
+  pub fn score(&self) -> usize {
+    self.rounds * self.hit_points as usize
+  }
+}
+
+/// ## The cave with the units fighting in it
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Combat {
+  /// Walls row by row, the edges of the map are walls
+  walls: Vec<Vec<bool>>,
+  /// Units in the order they took their turns last round, dead ones included
+  pub units: Vec<Unit>,
+  /// Full rounds completed
+  pub rounds: usize,
+}
+
+impl Combat {
+  /// ## Parse the map of the puzzle input
+  ///
+  /// ### Example
+  /// ```
+  /// let combat = day_15::Combat::parse( include_str!( "../examples/movement.txt" ).split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+  /// assert_eq!( combat.units.len(), 6 );
+  ///
+  /// let error = day_15::Combat::parse( "###\n#?#\n###\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid square '?' (bytes 5..6)" );
+  ///
+  /// let error = day_15::Combat::parse( "###\n#G.\n###\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "the cave isn't closed off by walls (bytes 6..7)" );
+  /// ```
+  ///
+  /// ### Errors
+  /// Fails on unknown squares or when the edges of the map aren't walls.
+  pub fn parse(input: &str) -> Result<Combat, AocError> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut walls = Vec::new();
+    let mut units = Vec::new();
+
+    for (y, line) in lines.iter().enumerate() {
+      let mut row = Vec::new();
+      for (x, square) in line.char_indices() {
+        let span = || {
+          let start = Span::of(input, line).start + x;
+          Span::new(start, start + square.len_utf8())
+        };
+        let edge = y == 0 || x == 0 || y + 1 == lines.len() || x + 1 == line.len();
+        if edge && square != '#' {
+          return Err(AocError::parse_at(span(), "the cave isn't closed off by walls"));
+        }
+
+        row.push(square == '#');
+        let race = match square {
+          '#' | '.' => continue,
+          'E' => Race::Elf,
+          'G' => Race::Goblin,
+          _ => return Err(AocError::parse_at(span(), format!("invalid square {:?}", square))),
+        };
+        units.push(Unit {
+          race,
+          position: (y, x),
+          hit_points: HIT_POINTS,
+          attack_power: ATTACK_POWER,
+        });
+      }
+      walls.push(row);
+    }
+
+    Ok(Combat {
+      walls,
+      units,
+      rounds: 0,
+    })
+  }
+
+  /// ## The same combat with a different attack power for the elves
+  pub fn with_elf_attack(&self, attack_power: i32) -> Combat {
+    let mut combat = self.clone();
+    for elf in combat.units.iter_mut().filter(|unit| unit.race == Race::Elf) {
+      elf.attack_power = attack_power;
+    }
+    combat
+  }
+
+  pub fn living(&self) -> impl Iterator<Item = &Unit> {
+    self.units.iter().filter(|unit| unit.is_alive())
+  }
+
+  /// Squares units can't move through: walls and living units
+  fn blocked(&self) -> Vec<Vec<bool>> {
+    let mut blocked = self.walls.clone();
+    for unit in self.living() {
+      blocked[unit.position.0][unit.position.1] = true;
+    }
+    blocked
+  }
+
+  /// Steps from `from` to every reachable square
+  fn distances(&self, from: (usize, usize), blocked: &[Vec<bool>]) -> Vec<Vec<Option<usize>>> {
+    let mut distances: Vec<Vec<Option<usize>>> = blocked.iter().map(|row| vec![None; row.len()]).collect();
+    distances[from.0][from.1] = Some(0);
+
+    let mut queue = VecDeque::from(vec![(from, 0)]);
+    while let Some((position, distance)) = queue.pop_front() {
+      for (y, x) in neighbours(position).iter().copied() {
+        let open = blocked.get(y).and_then(|row| row.get(x)) == Some(&false);
+        if open && distances[y][x].is_none() {
+          distances[y][x] = Some(distance + 1);
+          queue.push_back(((y, x), distance + 1));
+        }
+      }
+    }
+    distances
+  }
+
+  fn enemy_of(&self, unit: usize, other: &Unit) -> bool {
+    other.is_alive() && other.race != self.units[unit].race
+  }
+
+  /// ## Move a unit one step towards the nearest square in range of an enemy
+  ///
+  /// Ties between squares, and between the first steps towards the chosen
+  /// square, are broken in reading order.  Returns whether the unit moved.
+  fn step(&mut self, unit: usize) -> bool {
+    let position = self.units[unit].position;
+    let blocked = self.blocked();
+    let open = |(y, x): (usize, usize)| blocked.get(y).and_then(|row| row.get(x)) == Some(&false);
+
+    let distances = self.distances(position, &blocked);
+    let chosen = self
+      .units
+      .iter()
+      .filter(|other| self.enemy_of(unit, other))
+      .flat_map(|enemy| neighbours(enemy.position).to_vec())
+      .filter(|&square| open(square))
+      .filter_map(|square| distances[square.0][square.1].map(|distance| (distance, square)))
+      .min();
+
+    if let Some((_, target)) = chosen {
+      let back = self.distances(target, &blocked);
+      let next = neighbours(position)
+        .iter()
+        .copied()
+        .filter(|&square| open(square))
+        .filter_map(|square| back[square.0][square.1].map(|distance| (distance, square)))
+        .min();
+      if let Some((_, square)) = next {
+        self.units[unit].position = square;
+        return true;
+      }
+    }
+    false
+  }
+
+  /// ## Attack the adjacent enemy with the fewest hit points
+  ///
+  /// Returns whether there was an enemy to attack.
+  fn attack(&mut self, unit: usize) -> bool {
+    let adjacent = neighbours(self.units[unit].position);
+    let target = (0..self.units.len())
+      .filter(|&other| self.enemy_of(unit, &self.units[other]))
+      .filter(|&other| adjacent.contains(&self.units[other].position))
+      .min_by_key(|&other| (self.units[other].hit_points, self.units[other].position));
+
+    if let Some(target) = target {
+      self.units[target].hit_points -= self.units[unit].attack_power;
+    }
+    target.is_some()
+  }
+
+  /// ## Let every unit take its turn, in reading order
+  ///
+  /// Returns whether the round was completed, it isn't when a unit finds no
+  /// targets left.
+  ///
+  /// ### Example
+  /// ```
+  /// let mut combat = day_15::Combat::parse( include_str!( "../examples/movement.txt" ).split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+  /// assert!( combat.round().unwrap() );
+  ///
+  /// let map: Vec<String> = combat.to_string().lines().map( |line| line.to_string() ).collect();
+  /// assert_eq!( map, vec![
+  ///   "#######",
+  ///   "#..G..#   G(200)",
+  ///   "#...EG#   E(197), G(197)",
+  ///   "#.#G#G#   G(200), G(197)",
+  ///   "#...#E#   E(197)",
+  ///   "#.....#",
+  ///   "#######",
+  /// ] );
+  ///
+  /// // The elf and the goblin can't reach each other
+  /// let mut combat = day_15::Combat::parse( "#######\n#E.#.G#\n#######\n" ).unwrap();
+  /// let error = combat.round().unwrap_err();
+  /// assert_eq!( error.to_string(), "no solution: nothing happens in round 1" );
+  /// ```
+  ///
+  /// ### Errors
+  /// Fails when no unit moves or attacks during a whole round, every round
+  /// after it would be the same.
+  pub fn round(&mut self) -> Result<bool, AocError> {
+    self.units.retain(Unit::is_alive);
+    self.units.sort_by_key(|unit| unit.position);
+
+    let mut active = false;
+    for unit in 0..self.units.len() {
+      if !self.units[unit].is_alive() {
+        continue;
+      }
+      if !self.units.iter().any(|other| self.enemy_of(unit, other)) {
+        return Ok(false);
+      }
+
+      let adjacent = neighbours(self.units[unit].position);
+      if !self
+        .units
+        .iter()
+        .any(|other| self.enemy_of(unit, other) && adjacent.contains(&other.position))
+      {
+        active |= self.step(unit);
+      }
+      active |= self.attack(unit);
+    }
+
+    if !active {
+      return Err(AocError::NoSolution(format!(
+        "nothing happens in round {}",
+        self.rounds + 1
+      )));
+    }
+    self.rounds += 1;
+    Ok(true)
+  }
+
+  fn elves(&self) -> usize {
+    self.living().filter(|unit| unit.race == Race::Elf).count()
+  }
+
+  /// ## Fight until one race is left
+  ///
+  /// With `spare_elves` the combat is abandoned as soon as an elf dies.
+  ///
+  /// ### Errors
+  /// Fails if the combat reaches a stalemate, see `round`.
+  pub fn fight(&mut self, spare_elves: bool) -> Result<Option<Outcome>, AocError> {
+    let elves = self.elves();
+    while self.round()? {
+      if spare_elves && self.elves() < elves {
+        return Ok(None);
+      }
+    }
+    if spare_elves && self.elves() < elves {
+      return Ok(None);
+    }
+
+    Ok(Some(Outcome {
+      rounds: self.rounds,
+      hit_points: self.living().map(|unit| unit.hit_points).sum(),
+    }))
+  }
+}
+
+impl fmt::Display for Combat {
+  /// The map as in the puzzle, followed by the hit points of the units on every row
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (y, row) in self.walls.iter().enumerate() {
+      let mut units: Vec<&Unit> = self.living().filter(|unit| unit.position.0 == y).collect();
+      units.sort_by_key(|unit| unit.position);
+
+      let mut line: String = row.iter().map(|&wall| if wall { '#' } else { '.' }).collect();
+      for unit in &units {
+        let x = unit.position.1;
+        line.replace_range(x..=x, &unit.race.symbol().to_string());
+      }
+
+      if !units.is_empty() {
+        let hit_points: Vec<String> = units
+          .iter()
+          .map(|unit| format!("{}({})", unit.race.symbol(), unit.hit_points))
+          .collect();
+        line.push_str("   ");
+        line.push_str(&hit_points.join(", "));
+      }
+      writeln!(f, "{}", line)?;
+    }
+    Ok(())
+  }
+}
+
+/// ## Part 1
+/// Find the outcome of the combat: full rounds times the hit points left.
+///
+/// ### Example
+/// ```
+/// let combat = day_15::Combat::parse( include_str!( "../examples/movement.txt" ).split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+/// assert_eq!( day_15::part_1( &combat ).unwrap(), 27730 );
+///
+/// // Walled off from each other, or no units at all
+/// let combat = day_15::Combat::parse( "#######\n#E.#.G#\n#######\n" ).unwrap();
+/// assert!( day_15::part_1( &combat ).is_err() );
+/// let combat = day_15::Combat::parse( "#####\n#...#\n#####\n" ).unwrap();
+/// assert!( day_15::part_1( &combat ).is_err() );
+/// ```
+///
+/// ### Errors
+/// Fails if the combat reaches a stalemate.
+pub fn part_1(combat: &Combat) -> Result<usize, AocError> {
+  let mut combat = combat.clone();
+  Ok(combat.fight(false)?.map_or(0, |outcome| outcome.score()))
+}
+
+/// ## Part 2
+/// Find the outcome of the combat with the lowest elf attack power at which
+/// no elf dies.
+///
+/// ### Example
+/// ```
+/// let combat = day_15::Combat::parse( include_str!( "../examples/movement.txt" ).split( "---\n" ).nth( 1 ).unwrap() ).unwrap();
+/// assert_eq!( day_15::part_2( &combat ).unwrap(), 4988 );
+///
+/// let combat = day_15::Combat::parse( "#######\n#E.#.G#\n#######\n" ).unwrap();
+/// assert!( day_15::part_2( &combat ).is_err() );
+/// ```
+///
+/// ### Errors
+/// Fails if elves die even when they kill with a single blow, or if the
+/// combat reaches a stalemate.
+pub fn part_2(combat: &Combat) -> Result<usize, AocError> {
+  for attack_power in ATTACK_POWER + 1..=HIT_POINTS {
+    if let Some(outcome) = combat.with_elf_attack(attack_power).fight(true)? {
+      return Ok(outcome.score());
+    }
+  }
+  Err(AocError::NoSolution("elves die at any attack power".to_string()))
+}
+
+/// # Beverage Bandits
+pub struct Day15;
+
+impl Solution for Day15 {
+  const DAY: u8 = 15;
+  const TITLE: &'static str = "Beverage Bandits";
+
+  type Input<'a> = Combat;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Combat, AocError> {
+    Combat::parse(input)
+  }
+
+  fn part_1(combat: &Combat) -> Result<usize, AocError> {
+    part_1(combat)
+  }
+
+  fn part_2(combat: &Combat) -> Result<usize, AocError> {
+    part_2(combat)
+  }
+}
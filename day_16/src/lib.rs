@@ -0,0 +1,255 @@
+//! # Day 16 of Advent of Code
+//!
+//! `day_16` contains the solution to the puzzle of
+//! [day 16](https://adventofcode.com/2018/day/16) of the 2018 edition of Advent of Code.
+
+use std::collections::BTreeSet;
+use utils::elfcode::{Instruction, Opcode, Registers};
+use utils::{AocError, Solution, Span};
+
+/// Number of registers of the device
+pub const REGISTERS: usize = 4;
+
+/// ## An instruction as it appears in the input, with a numbered opcode
+pub type Code = [usize; 4];
+
+/// ## Effect of an instruction on the registers
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Sample {
+  pub before: Registers,
+  pub code: Code,
+  pub after: Registers,
+}
+
+impl Sample {
+  /// ## Opcodes that have the effect of the sample
+  ///
+  /// ### Example
+  /// ```
+  /// use day_16::Sample;
+  /// use utils::elfcode::{Opcode, Registers};
+  ///
+  /// let sample = Sample {
+  ///   before: Registers( vec![ 3, 2, 1, 1 ] ),
+  ///   code: [ 9, 2, 1, 2 ],
+  ///   after: Registers( vec![ 3, 2, 2, 1 ] ),
+  /// };
+  /// assert_eq!( sample.candidates(), vec![ Opcode::Addi, Opcode::Mulr, Opcode::Seti ] );
+  /// ```
+  pub fn candidates(&self) -> Vec<Opcode> {
+    let [_, a, b, c] = self.code;
+    Opcode::ALL
+      .iter()
+      .copied()
+      .filter(|opcode| {
+        let mut registers = self.before.clone();
+        opcode.execute(&mut registers, a, b, c).is_some() && registers == self.after
+      })
+      .collect()
+  }
+}
+
+/// ## Samples and test program of the puzzle input
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Manual {
+  pub samples: Vec<Sample>,
+  pub program: Vec<Code>,
+}
+
+fn code(line: &str, input: &str) -> Result<Code, AocError> {
+  let fields: Vec<&str> = line.split_whitespace().collect();
+  if fields.len() != 4 {
+    return Err(AocError::parse_at(
+      Span::of(input, line),
+      format!("invalid instruction \"{}\"", line),
+    ));
+  }
+
+  let mut code = [0; 4];
+  for (value, field) in code.iter_mut().zip(fields) {
+    *value = field.parse().map_err(|_| {
+      AocError::parse_at(Span::of(input, field), format!("invalid number \"{}\"", field))
+    })?;
+  }
+  Ok(code)
+}
+
+fn registers(line: &str, prefix: &str, input: &str) -> Result<Registers, AocError> {
+  let list = line
+    .strip_prefix(prefix)
+    .ok_or_else(|| AocError::parse_at(Span::of(input, line), format!("expected \"{}\"", prefix.trim())))?;
+  let registers = Registers::parse(list).map_err(|err| err.within(Span::of(input, list)))?;
+  if registers.0.len() != REGISTERS {
+    return Err(AocError::parse_at(
+      Span::of(input, list),
+      format!("expected {} registers", REGISTERS),
+    ));
+  }
+  Ok(registers)
+}
+
+impl Manual {
+  /// ## Parse the puzzle input
+  ///
+  /// ### Example
+  /// ```
+  /// let manual = day_16::Manual::parse( "Before: [3, 2, 1, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]\n\n\n\n9 2 1 2\n" ).unwrap();
+  /// assert_eq!( manual.samples.len(), 1 );
+  /// assert_eq!( manual.program, vec![ [ 9, 2, 1, 2 ] ] );
+  ///
+  /// let error = day_16::Manual::parse( "Before: [3, 2, 1, 1]\n9 2 1 2\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "the sample ends early (bytes 0..20)" );
+  ///
+  /// let error = day_16::Manual::parse( "Before: [3, 2, 1, 1]\n9 2 x 2\nAfter:  [3, 2, 2, 1]\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid number \"x\" (bytes 25..26)" );
+  /// ```
+  pub fn parse(input: &str) -> Result<Manual, AocError> {
+    let mut samples = Vec::new();
+    let mut program = Vec::new();
+    let mut lines = input.lines().filter(|line| !line.trim().is_empty());
+
+    while let Some(line) = lines.next() {
+      if line.starts_with("Before:") {
+        let early = || AocError::parse_at(Span::of(input, line), "the sample ends early");
+        let before = registers(line, "Before:", input)?;
+        let code = code(lines.next().ok_or_else(early)?, input)?;
+        let after = registers(lines.next().ok_or_else(early)?, "After:", input)?;
+        samples.push(Sample { before, code, after });
+      } else {
+        program.push(code(line, input)?);
+      }
+    }
+
+    Ok(Manual { samples, program })
+  }
+}
+
+/// ## Part 1
+/// Count the samples that behave like three or more opcodes.
+///
+/// ### Example
+/// ```
+/// let manual = day_16::Manual::parse( "Before: [3, 2, 1, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]\n" ).unwrap();
+/// assert_eq!( day_16::part_1( &manual ), 1 );
+/// ```
+pub fn part_1(manual: &Manual) -> usize {
+  manual
+    .samples
+    .iter()
+    .filter(|sample| sample.candidates().len() >= 3)
+    .count()
+}
+
+/// ## Work out the opcode of every number from the samples
+///
+/// Every sample rules out the opcodes that don't match it.  A number with a
+/// single opcode left is settled, which rules that opcode out for all other
+/// numbers, until every number is settled.
+///
+/// ### Example
+/// ```
+/// use day_16::{Manual, Sample};
+/// use utils::elfcode::{Opcode, Registers};
+///
+/// let input = include_str!( "../examples/program.txt" ).split( "---\n" ).nth( 1 ).unwrap();
+/// let manual = Manual::parse( input ).unwrap();
+/// let opcodes = day_16::opcodes( &manual.samples ).unwrap();
+/// assert_eq!( opcodes.len(), 16 );
+/// assert_eq!( opcodes[13], Opcode::Seti );
+/// assert_eq!( opcodes[10], Opcode::Addr );
+/// assert_eq!( opcodes[1], Opcode::Muli );
+///
+/// // Addi, mulr and seti all match the only sample, and the other numbers
+/// // have no samples at all
+/// let samples = vec![
+///   Sample { before: Registers( vec![ 3, 2, 1, 1 ] ), code: [ 9, 2, 1, 2 ], after: Registers( vec![ 3, 2, 2, 1 ] ) },
+/// ];
+/// assert!( day_16::opcodes( &samples ).is_err() );
+/// ```
+///
+/// ### Errors
+/// Fails if the samples contradict each other or don't settle every number.
+pub fn opcodes(samples: &[Sample]) -> Result<Vec<Opcode>, AocError> {
+  let all: BTreeSet<Opcode> = Opcode::ALL.iter().copied().collect();
+  let mut possible = vec![all; Opcode::ALL.len()];
+
+  for sample in samples {
+    let number = sample.code[0];
+    let candidates: BTreeSet<Opcode> = sample.candidates().into_iter().collect();
+    let options = possible
+      .get_mut(number)
+      .ok_or_else(|| AocError::InvalidInput(format!("there is no opcode {}", number)))?;
+    *options = options.intersection(&candidates).copied().collect();
+  }
+
+  let mut settled: Vec<Option<Opcode>> = vec![None; Opcode::ALL.len()];
+  while let Some(number) = (0..possible.len()).find(|&number| settled[number].is_none() && possible[number].len() == 1) {
+    let opcode = *possible[number].iter().next().expect("a single option is left");
+    settled[number] = Some(opcode);
+    for (other, options) in possible.iter_mut().enumerate() {
+      if other != number {
+        options.remove(&opcode);
+      }
+    }
+  }
+
+  settled
+    .into_iter()
+    .enumerate()
+    .map(|(number, opcode)| {
+      opcode.ok_or_else(|| AocError::NoSolution(format!("the samples don't settle opcode {}", number)))
+    })
+    .collect()
+}
+
+/// ## Part 2
+/// Find the value of register 0 after running the test program.
+///
+/// ### Example
+/// ```
+/// // The program computes ( 7 + 5 ) * 3
+/// let input = include_str!( "../examples/program.txt" ).split( "---\n" ).nth( 1 ).unwrap();
+/// let manual = day_16::Manual::parse( input ).unwrap();
+/// assert_eq!( manual.program.len(), 4 );
+/// assert_eq!( day_16::part_2( &manual ).unwrap(), 36 );
+/// ```
+///
+/// ### Errors
+/// Fails if the opcodes can't be worked out or the program uses an unknown
+/// opcode or register.
+pub fn part_2(manual: &Manual) -> Result<usize, AocError> {
+  let opcodes = opcodes(&manual.samples)?;
+  let mut registers = Registers::new(REGISTERS);
+
+  for &[number, a, b, c] in &manual.program {
+    let opcode = *opcodes
+      .get(number)
+      .ok_or_else(|| AocError::InvalidInput(format!("there is no opcode {}", number)))?;
+    Instruction { opcode, a, b, c }.execute(&mut registers)?;
+  }
+  Ok(registers.0[0])
+}
+
+/// # Chronal Classification
+pub struct Day16;
+
+impl Solution for Day16 {
+  const DAY: u8 = 16;
+  const TITLE: &'static str = "Chronal Classification";
+
+  type Input<'a> = Manual;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Manual, AocError> {
+    Manual::parse(input)
+  }
+
+  fn part_1(manual: &Manual) -> Result<usize, AocError> {
+    Ok(part_1(manual))
+  }
+
+  fn part_2(manual: &Manual) -> Result<usize, AocError> {
+    part_2(manual)
+  }
+}
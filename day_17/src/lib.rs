@@ -0,0 +1,396 @@
+//! # Day 17 of Advent of Code
+//!
+//! `day_17` contains the solution to the puzzle of
+//! [day 17](https://adventofcode.com/2018/day/17) of the 2018 edition of Advent of Code.
+
+use std::fmt;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use utils::{parse_lines, AocError, Solution, Span};
+
+/// Location of the spring, as `(x, y)`
+pub const SPRING: (usize, usize) = (500, 0);
+
+/// ## A vein of clay, either a vertical or a horizontal line
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Vein {
+  pub x: RangeInclusive<usize>,
+  pub y: RangeInclusive<usize>,
+}
+
+impl Vein {
+  /// ## Parse an input line into a vein
+  ///
+  /// ### Example
+  /// ```
+  /// use day_17::Vein;
+  ///
+  /// assert_eq!( Vein::parse( "x=495, y=2..7" ).unwrap(), Vein { x: 495..=495, y: 2..=7 } );
+  /// assert_eq!( Vein::parse( "y=7, x=495..501" ).unwrap(), Vein { x: 495..=501, y: 7..=7 } );
+  ///
+  /// let error = Vein::parse( "x=495, y=2..seven" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid number \"seven\" (bytes 12..17)" );
+  ///
+  /// assert!( Vein::parse( "x=495, x=2..7" ).is_err() );
+  /// assert!( Vein::parse( "x=495, y=7..2" ).is_err() );
+  /// ```
+  pub fn parse(line: &str) -> Result<Vein, AocError> {
+    let invalid = || AocError::parse(format!("invalid vein \"{}\"", line));
+    let (first, second) = line.split_once(", ").ok_or_else(invalid)?;
+
+    let number = |field: &str| {
+      field.parse::<usize>().map_err(|_| {
+        AocError::parse_at(Span::of(line, field), format!("invalid number \"{}\"", field))
+      })
+    };
+    let range = |field: &str| -> Result<RangeInclusive<usize>, AocError> {
+      let (start, end) = field.split_once("..").unwrap_or((field, field));
+      let (start, end) = (number(start)?, number(end)?);
+      if start > end {
+        return Err(AocError::parse_at(Span::of(line, field), "the range is empty"));
+      }
+      Ok(start..=end)
+    };
+
+    match (first.split_once('='), second.split_once('=')) {
+      (Some(("x", x)), Some(("y", y))) | (Some(("y", y)), Some(("x", x))) => Ok(Vein {
+        x: range(x)?,
+        y: range(y)?,
+      }),
+      _ => Err(invalid()),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Tile {
+  Sand,
+  Clay,
+  /// Water that passes through, `|` on the map
+  Flowing,
+  /// Water that comes to rest, `~` on the map
+  Settled,
+}
+
+impl Tile {
+  fn is_wet(self) -> bool {
+    self == Tile::Flowing || self == Tile::Settled
+  }
+
+  /// Water on top of this tile spreads sideways instead of falling
+  fn is_support(self) -> bool {
+    self == Tile::Clay || self == Tile::Settled
+  }
+}
+
+/// ## The scanned slice of ground, from the spring down to the deepest clay
+///
+/// The slice is one column wider than the clay on both sides, so water can
+/// fall past the outermost veins.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Ground {
+  tiles: Vec<Tile>,
+  /// Leftmost `x` of the slice
+  left: usize,
+  width: usize,
+  /// Rows of the scan, from the highest to the lowest clay
+  scan: RangeInclusive<usize>,
+}
+
+impl Ground {
+  /// ## Ground with the clay of the veins and no water yet
+  ///
+  /// ### Example
+  /// ```
+  /// let veins = vec![ day_17::Vein { x: 0..=0, y: 1..=3 } ];
+  /// let error = day_17::Ground::new( &veins ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid input: there is no column left of the clay at x=0" );
+  ///
+  /// let veins = vec![ day_17::Vein { x: 1..=1, y: 1..=3 } ];
+  /// assert!( day_17::Ground::new( &veins ).is_ok() );
+  /// ```
+  ///
+  /// ### Errors
+  /// Fails without veins, or with clay at the leftmost or rightmost `x`, which
+  /// leaves no column for the water to fall past it.
+  pub fn new(veins: &[Vein]) -> Result<Ground, AocError> {
+    if veins.is_empty() {
+      return Err(AocError::InvalidInput("the scan has no clay".to_string()));
+    }
+
+    let left = veins.iter().map(|vein| *vein.x.start()).min().unwrap_or(0);
+    let left = left.min(SPRING.0).checked_sub(1).ok_or_else(|| {
+      AocError::InvalidInput("there is no column left of the clay at x=0".to_string())
+    })?;
+    let right = veins.iter().map(|vein| *vein.x.end()).max().unwrap_or(0);
+    let right = right.max(SPRING.0).checked_add(1).ok_or_else(|| {
+      AocError::InvalidInput(format!("there is no column right of the clay at x={}", usize::MAX))
+    })?;
+    let top = veins.iter().map(|vein| *vein.y.start()).min().unwrap_or(0);
+    let bottom = veins.iter().map(|vein| *vein.y.end()).max().unwrap_or(0);
+
+    let width = right - left + 1;
+    let mut ground = Ground {
+      tiles: vec![Tile::Sand; width * (bottom + 1)],
+      left,
+      width,
+      scan: top..=bottom,
+    };
+    for vein in veins {
+      for y in vein.y.clone() {
+        for x in vein.x.clone() {
+          let index = ground.index(x, y);
+          ground.tiles[index] = Tile::Clay;
+        }
+      }
+    }
+    Ok(ground)
+  }
+
+  /// ## Ground after the water of the spring has spread as far as it goes
+  ///
+  /// ### Errors
+  /// Fails without veins or with clay at the edge of `x`, see `Ground::new`.
+  pub fn flooded(veins: &[Vein]) -> Result<Ground, AocError> {
+    let mut ground = Ground::new(veins)?;
+    ground.flow();
+    Ok(ground)
+  }
+
+  fn index(&self, x: usize, y: usize) -> usize {
+    y * self.width + x - self.left
+  }
+
+  pub fn tile(&self, x: usize, y: usize) -> Tile {
+    self.tiles[self.index(x, y)]
+  }
+
+  fn set(&mut self, x: usize, y: usize, tile: Tile) {
+    let index = self.index(x, y);
+    self.tiles[index] = tile;
+  }
+
+  fn bottom(&self) -> usize {
+    *self.scan.end()
+  }
+
+  /// ## Let the water of the spring flow until nothing changes
+  ///
+  /// Every tile on the stack holds water that still has to move.  Water falls
+  /// until it lands on clay or settled water, and then spreads sideways.  A
+  /// row closed in by clay on both sides settles, which lets the water that
+  /// feeds it from above spread in turn.  Otherwise the water flows over the
+  /// open sides and falls again.
+  ///
+  /// ### Example
+  /// ```
+  /// let veins = vec![
+  ///   day_17::Vein { x: 499..=499, y: 1..=2 },
+  ///   day_17::Vein { x: 499..=501, y: 3..=3 },
+  ///   day_17::Vein { x: 501..=501, y: 1..=2 },
+  /// ];
+  /// let mut ground = day_17::Ground::new( &veins ).unwrap();
+  /// ground.flow();
+  /// assert_eq!( ground.to_string(), "||+||\n|#~#|\n|#~#|\n|###|\n" );
+  /// ```
+  pub fn flow(&mut self) {
+    let mut stack = vec![SPRING];
+
+    while let Some((x, mut y)) = stack.pop() {
+      // Water pushed twice can have settled in the meantime
+      if self.tile(x, y) == Tile::Settled {
+        continue;
+      }
+
+      // Fall
+      loop {
+        self.set(x, y, Tile::Flowing);
+        if y == self.bottom() {
+          break;
+        }
+        match self.tile(x, y + 1) {
+          Tile::Sand => y += 1,
+          _ => break,
+        }
+      }
+      if y == self.bottom() || !self.tile(x, y + 1).is_support() {
+        continue;
+      }
+
+      // Spread, the outer columns never hold clay so both sides end in the slice
+      let (left, left_closed) = self.spread(x, y, |x| x - 1);
+      let (right, right_closed) = self.spread(x, y, |x| x + 1);
+      if left_closed && right_closed {
+        for x in left..=right {
+          self.set(x, y, Tile::Settled);
+        }
+        if y > 0 {
+          let fed = (left..=right).filter(|&x| self.tile(x, y - 1) == Tile::Flowing);
+          stack.extend(fed.map(|x| (x, y - 1)).collect::<Vec<_>>());
+        }
+      } else {
+        for x in left..=right {
+          self.set(x, y, Tile::Flowing);
+        }
+        if !left_closed {
+          stack.push((left, y));
+        }
+        if !right_closed {
+          stack.push((right, y));
+        }
+      }
+    }
+  }
+
+  /// Last column water reaches in a direction, and whether clay stops it there
+  fn spread<F: Fn(usize) -> usize>(&self, mut x: usize, y: usize, step: F) -> (usize, bool) {
+    loop {
+      if !self.tile(x, y + 1).is_support() {
+        return (x, false);
+      }
+      if self.tile(step(x), y) == Tile::Clay {
+        return (x, true);
+      }
+      x = step(x);
+    }
+  }
+
+  fn count<P: Fn(Tile) -> bool>(&self, predicate: P) -> usize {
+    let rows = self.tiles.chunks(self.width);
+    rows
+      .skip(*self.scan.start())
+      .map(|row| row.iter().filter(|&&tile| predicate(tile)).count())
+      .sum()
+  }
+
+  /// ## Number of tiles the water reaches within the scan
+  pub fn reached(&self) -> usize {
+    self.count(Tile::is_wet)
+  }
+
+  /// ## Number of tiles holding settled water within the scan
+  pub fn retained(&self) -> usize {
+    self.count(|tile| tile == Tile::Settled)
+  }
+
+  /// ## Write the map to a file, for inspection
+  ///
+  /// ### Example
+  /// ```
+  /// let veins = vec![ day_17::Vein { x: 500..=500, y: 1..=1 } ];
+  /// let ground = day_17::Ground::flooded( &veins ).unwrap();
+  ///
+  /// let path = std::env::temp_dir().join( "day_17_dump.txt" );
+  /// ground.dump( &path ).unwrap();
+  /// assert_eq!( std::fs::read_to_string( &path ).unwrap(), "|+|\n|#|\n" );
+  /// ```
+  ///
+  /// ### Errors
+  /// Fails if the file can't be written.
+  pub fn dump<P: AsRef<Path>>(&self, path: P) -> Result<(), AocError> {
+    fs::write(path, self.to_string())?;
+    Ok(())
+  }
+}
+
+impl fmt::Display for Ground {
+  /// The map as in the puzzle, with `#` for clay and `|` and `~` for water
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (y, row) in self.tiles.chunks(self.width).enumerate() {
+      let row: String = row
+        .iter()
+        .enumerate()
+        .map(|(column, tile)| match tile {
+          _ if (self.left + column, y) == SPRING => '+',
+          Tile::Sand => '.',
+          Tile::Clay => '#',
+          Tile::Flowing => '|',
+          Tile::Settled => '~',
+        })
+        .collect();
+      writeln!(f, "{}", row)?;
+    }
+    Ok(())
+  }
+}
+
+/// ## Part 1
+/// Count the tiles within the scan the water reaches.
+///
+/// ### Example
+/// ```
+/// let input = include_str!( "../examples/reservoir.txt" ).split( "---\n" ).nth( 1 ).unwrap();
+/// let veins = day_17::veins( input ).unwrap();
+/// assert_eq!( day_17::part_1( &veins ).unwrap(), 57 );
+///
+/// let map = day_17::Ground::flooded( &veins ).unwrap().to_string();
+/// assert_eq!( map.lines().collect::<Vec<_>>(), vec![
+///   "......+.......",
+///   "......|.....#.",
+///   ".#..#||||...#.",
+///   ".#..#~~#|.....",
+///   ".#..#~~#|.....",
+///   ".#~~~~~#|.....",
+///   ".#~~~~~#|.....",
+///   ".#######|.....",
+///   "........|.....",
+///   "...|||||||||..",
+///   "...|#~~~~~#|..",
+///   "...|#~~~~~#|..",
+///   "...|#~~~~~#|..",
+///   "...|#######|..",
+/// ] );
+/// ```
+///
+/// ### Errors
+/// Fails without veins or with clay at the edge of `x`, see `Ground::new`.
+pub fn part_1(veins: &[Vein]) -> Result<usize, AocError> {
+  Ground::flooded(veins).map(|ground| ground.reached())
+}
+
+/// ## Part 2
+/// Count the tiles within the scan that keep their water once the spring
+/// runs dry.
+///
+/// ### Example
+/// ```
+/// let input = include_str!( "../examples/reservoir.txt" ).split( "---\n" ).nth( 1 ).unwrap();
+/// let veins = day_17::veins( input ).unwrap();
+/// assert_eq!( day_17::part_2( &veins ).unwrap(), 29 );
+/// ```
+///
+/// ### Errors
+/// Fails without veins or with clay at the edge of `x`, see `Ground::new`.
+pub fn part_2(veins: &[Vein]) -> Result<usize, AocError> {
+  Ground::flooded(veins).map(|ground| ground.retained())
+}
+
+/// ## Parse the veins of the puzzle input
+pub fn veins(input: &str) -> Result<Vec<Vein>, AocError> {
+  parse_lines(input, Vein::parse)
+}
+
+/// # Reservoir Research
+pub struct Day17;
+
+impl Solution for Day17 {
+  const DAY: u8 = 17;
+  const TITLE: &'static str = "Reservoir Research";
+
+  type Input<'a> = Vec<Vein>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Vec<Vein>, AocError> {
+    veins(input)
+  }
+
+  fn part_1(veins: &Vec<Vein>) -> Result<usize, AocError> {
+    part_1(veins)
+  }
+
+  fn part_2(veins: &Vec<Vein>) -> Result<usize, AocError> {
+    part_2(veins)
+  }
+}
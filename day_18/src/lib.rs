@@ -0,0 +1,205 @@
+//! # Day 18 of Advent of Code
+//!
+//! `day_18` contains the solution to the puzzle of
+//! [day 18](https://adventofcode.com/2018/day/18) of the 2018 edition of Advent of Code.
+
+use std::fmt;
+use utils::{AocError, Solution, Span};
+
+/// Minutes of part 1 in the puzzle
+pub const MINUTES_1: usize = 10;
+/// Minutes of part 2 in the puzzle
+pub const MINUTES_2: usize = 1_000_000_000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Acre {
+  Open,
+  Trees,
+  Lumberyard,
+}
+
+/// ## The lumber collection area, acre by acre
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Area {
+  acres: Vec<Acre>,
+  pub width: usize,
+  pub height: usize,
+}
+
+impl Area {
+  /// ## Parse the map of the puzzle input
+  ///
+  /// ### Example
+  /// ```
+  /// let area = day_18::Area::parse( ".#|\n||.\n" ).unwrap();
+  /// assert_eq!( ( area.width, area.height ), ( 3, 2 ) );
+  ///
+  /// let error = day_18::Area::parse( ".#|\n|x.\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid acre 'x' (bytes 5..6)" );
+  ///
+  /// let error = day_18::Area::parse( ".#|\n||\n" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "expected 3 acres (bytes 4..6)" );
+  /// ```
+  ///
+  /// ### Errors
+  /// Fails on an unknown acre or rows of different lengths.
+  pub fn parse(input: &str) -> Result<Area, AocError> {
+    let mut acres = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+
+    for line in input.lines().filter(|line| !line.is_empty()) {
+      let start = Span::of(input, line).start;
+      for (index, acre) in line.char_indices() {
+        acres.push(match acre {
+          '.' => Acre::Open,
+          '|' => Acre::Trees,
+          '#' => Acre::Lumberyard,
+          other => {
+            return Err(AocError::parse_at(
+              Span::new(start + index, start + index + other.len_utf8()),
+              format!("invalid acre {:?}", other),
+            ))
+          }
+        });
+      }
+
+      let expected = *width.get_or_insert(line.len());
+      if line.len() != expected {
+        return Err(AocError::parse_at(
+          Span::of(input, line),
+          format!("expected {} acres", expected),
+        ));
+      }
+      height += 1;
+    }
+
+    if height == 0 {
+      return Err(AocError::parse("the area is empty"));
+    }
+    Ok(Area {
+      acres,
+      width: width.unwrap_or(0),
+      height,
+    })
+  }
+
+  pub fn acre(&self, x: usize, y: usize) -> Acre {
+    self.acres[y * self.width + x]
+  }
+
+  /// Acres around an acre, fewer along the edges
+  fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = Acre> + '_ {
+    let rows = y.saturating_sub(1)..=(y + 1).min(self.height - 1);
+    rows.flat_map(move |ny| {
+      let columns = x.saturating_sub(1)..=(x + 1).min(self.width - 1);
+      columns
+        .filter(move |&nx| (nx, ny) != (x, y))
+        .map(move |nx| self.acre(nx, ny))
+    })
+  }
+
+  /// ## The area a minute later
+  ///
+  /// ### Example
+  /// ```
+  /// let area = day_18::Area::parse( "|||\n|.|\n...\n" ).unwrap();
+  /// assert_eq!( area.minute().to_string(), "|||\n|||\n...\n" );
+  /// ```
+  pub fn minute(&self) -> Area {
+    let mut acres = Vec::with_capacity(self.acres.len());
+    for y in 0..self.height {
+      for x in 0..self.width {
+        let count = |kind: Acre| self.neighbours(x, y).filter(|&acre| acre == kind).count();
+        acres.push(match self.acre(x, y) {
+          Acre::Open if count(Acre::Trees) >= 3 => Acre::Trees,
+          Acre::Trees if count(Acre::Lumberyard) >= 3 => Acre::Lumberyard,
+          Acre::Lumberyard if count(Acre::Lumberyard) == 0 || count(Acre::Trees) == 0 => Acre::Open,
+          acre => acre,
+        });
+      }
+    }
+    Area { acres, ..*self }
+  }
+
+  /// ## Wooded acres times lumberyards
+  pub fn resource_value(&self) -> usize {
+    let count = |kind: Acre| self.acres.iter().filter(|&&acre| acre == kind).count();
+    count(Acre::Trees) * count(Acre::Lumberyard)
+  }
+}
+
+impl fmt::Display for Area {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for row in self.acres.chunks(self.width) {
+      let row: String = row
+        .iter()
+        .map(|acre| match acre {
+          Acre::Open => '.',
+          Acre::Trees => '|',
+          Acre::Lumberyard => '#',
+        })
+        .collect();
+      writeln!(f, "{}", row)?;
+    }
+    Ok(())
+  }
+}
+
+/// ## Part 1
+/// Find the resource value after a few minutes, one minute at a time.
+///
+/// ### Example
+/// ```
+/// let input = include_str!( "../examples/lumber.txt" ).split( "---\n" ).nth( 1 ).unwrap();
+/// let area = day_18::Area::parse( input ).unwrap();
+/// assert_eq!( day_18::part_1( &area, 10 ), 1147 );
+/// ```
+pub fn part_1(area: &Area, minutes: usize) -> usize {
+  let mut area = area.clone();
+  for _ in 0..minutes {
+    area = area.minute();
+  }
+  area.resource_value()
+}
+
+/// ## Part 2
+/// Find the resource value after many minutes, skipping ahead once the area
+/// starts repeating itself.
+///
+/// ### Example
+/// ```
+/// let input = include_str!( "../examples/lumber.txt" ).split( "---\n" ).nth( 1 ).unwrap();
+/// let area = day_18::Area::parse( input ).unwrap();
+/// assert_eq!( day_18::part_2( &area, 10 ), 1147 );
+///
+/// // The example ends up without trees
+/// assert_eq!( day_18::part_2( &area, 1_000_000_000 ), 0 );
+/// ```
+pub fn part_2(area: &Area, minutes: usize) -> usize {
+  utils::cycle::nth(area.clone(), Area::minute, minutes).resource_value()
+}
+
+/// # Settlers of The North Pole
+pub struct Day18;
+
+impl Solution for Day18 {
+  const DAY: u8 = 18;
+  const TITLE: &'static str = "Settlers of The North Pole";
+
+  type Input<'a> = Area;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Area, AocError> {
+    Area::parse(input)
+  }
+
+  fn part_1(area: &Area) -> Result<usize, AocError> {
+    Ok(part_1(area, MINUTES_1))
+  }
+
+  fn part_2(area: &Area) -> Result<usize, AocError> {
+    Ok(part_2(area, MINUTES_2))
+  }
+}
@@ -5,6 +5,7 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::FromIterator;
+use utils::{AocError, Solution};
 
 /// ## First part of the exercise
 ///
@@ -59,9 +60,12 @@ pub fn letter_counts(string: &str) -> HashSet<i32> {
 ///
 /// ```
 /// let input = vec![ "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz" ];
-/// assert_eq!( day_2::part_2( &input ), "fgij" );
+/// assert_eq!( day_2::part_2( &input ).unwrap(), "fgij" );
 /// ```
-pub fn part_2(input: &Vec<&str>) -> String {
+///
+/// ### Errors
+/// Fails if no pair of words differs in exactly one character.
+pub fn part_2(input: &Vec<&str>) -> Result<String, AocError> {
   let mut pairs = input
     .iter()
     .enumerate()
@@ -69,8 +73,8 @@ pub fn part_2(input: &Vec<&str>) -> String {
 
   let correct = pairs
     .find(|(first, second)| correct_input(first, second))
-    .expect("Could not find correct pair");
-  common_string(correct.0, correct.1)
+    .ok_or_else(|| AocError::NoSolution("could not find correct pair".to_string()))?;
+  Ok(common_string(correct.0, correct.1))
 }
 
 /// ### Get the common part of two correct words
@@ -126,3 +130,27 @@ pub fn correct_input(a: &str, b: &str) -> bool {
     diff == 1
   }
 }
+
+/// # Inventory Management System
+pub struct Day2;
+
+impl Solution for Day2 {
+  const DAY: u8 = 2;
+  const TITLE: &'static str = "Inventory Management System";
+
+  type Input<'a> = Vec<&'a str>;
+  type Output1 = i32;
+  type Output2 = String;
+
+  fn parse(input: &str) -> Result<Vec<&str>, AocError> {
+    Ok(input.lines().collect())
+  }
+
+  fn part_1(lines: &Vec<&str>) -> Result<i32, AocError> {
+    Ok(part_1(lines))
+  }
+
+  fn part_2(lines: &Vec<&str>) -> Result<String, AocError> {
+    part_2(lines)
+  }
+}
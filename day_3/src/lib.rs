@@ -3,6 +3,7 @@ extern crate regex;
 use regex::Regex;
 use std::collections::HashSet;
 use std::iter::FromIterator;
+use utils::{AocError, Solution, Span};
 
 #[derive(Debug, PartialEq)]
 pub struct Claim {
@@ -70,28 +71,43 @@ impl Claim {
     ///
     /// ### Example
     /// ```
-    /// assert_eq!( day_3::Claim::parse( "#1 @ 1,3: 4x4" ), day_3::Claim::new(1, 1, 3, 4, 4 ) );
-    /// assert_eq!( day_3::Claim::parse( "#2 @ 3,1: 4x4" ), day_3::Claim::new(2, 3, 1, 4, 4 ) );
-    /// assert_eq!( day_3::Claim::parse( "#3 @ 5,5: 2x3" ), day_3::Claim::new(3, 5, 5, 2, 3 ) );
+    /// assert_eq!( day_3::Claim::parse( "#1 @ 1,3: 4x4" ).unwrap(), day_3::Claim::new(1, 1, 3, 4, 4 ) );
+    /// assert_eq!( day_3::Claim::parse( "#2 @ 3,1: 4x4" ).unwrap(), day_3::Claim::new(2, 3, 1, 4, 4 ) );
+    /// assert_eq!( day_3::Claim::parse( "#3 @ 5,5: 2x3" ).unwrap(), day_3::Claim::new(3, 5, 5, 2, 3 ) );
+    ///
+    /// assert!( day_3::Claim::parse( "#4 @ 5,5: 2by3" ).is_err() );
+    ///
+    /// let error = day_3::Claim::parse( "#5 @ 5,5: 99999999999x3" ).unwrap_err();
+    /// assert_eq!( error.to_string(), "width out of range (bytes 10..21)" );
     /// ```
-    /// TODO: add error handling
-    pub fn parse(input: &str) -> Claim {
+    ///
+    /// ### Errors
+    /// Fails if the line isn't formatted as `#id @ x,y: widthxheight`.  A
+    /// number that is out of range is pointed at by the error's span.
+    pub fn parse(input: &str) -> Result<Claim, AocError> {
         let regex = Regex::new(
-            r"#(?P<id>[0-9]+) @ (?P<x>[0-9]+),(?P<y>[0-9]+): (?P<width>[0-9]+)x(?P<height>[0-9]+)",
+            r"^#(?P<id>[0-9]+) @ (?P<x>[0-9]+),(?P<y>[0-9]+): (?P<width>[0-9]+)x(?P<height>[0-9]+)$",
         )
         .unwrap();
-        let capt = regex.captures(input).unwrap();
-        let id = capt.name("id").unwrap().as_str().parse::<i32>().unwrap();
-        let x = capt.name("x").unwrap().as_str().parse::<i32>().unwrap();
-        let y = capt.name("y").unwrap().as_str().parse::<i32>().unwrap();
-        let width = capt.name("width").unwrap().as_str().parse::<i32>().unwrap();
-        let height = capt
-            .name("height")
-            .unwrap()
-            .as_str()
-            .parse::<i32>()
-            .unwrap();
-        Claim::new(id, x, y, width, height)
+        let capt = regex
+            .captures(input)
+            .ok_or_else(|| AocError::parse(format!("invalid claim {:?}", input)))?;
+        let number = |name: &str| {
+            let field = capt.name(name).unwrap();
+            field.as_str().parse::<i32>().map_err(|_| {
+                AocError::parse_at(
+                    Span::new(field.start(), field.end()),
+                    format!("{} out of range", name),
+                )
+            })
+        };
+        Ok(Claim::new(
+            number("id")?,
+            number("x")?,
+            number("y")?,
+            number("width")?,
+            number("height")?,
+        ))
     }
 
     pub fn new(id: i32, x: i32, y: i32, width: i32, height: i32) -> Claim {
@@ -220,10 +236,10 @@ impl Claim {
 /// ### Example
 /// ```
 /// let input = vec![ "#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2" ];
-/// assert_eq!( day_3::part_1( &input ), 4 );
+/// let claims: Vec<day_3::Claim> = input.iter().map( |line| day_3::Claim::parse( line ).unwrap() ).collect();
+/// assert_eq!( day_3::part_1( &claims ), 4 );
 /// ```
-pub fn part_1(input: &Vec<&str>) -> usize {
-    let claims: Vec<Claim> = input.iter().map(|line| Claim::parse(line)).collect();
+pub fn part_1(claims: &[Claim]) -> usize {
     let pairs = claims.iter().enumerate().flat_map(|(index, claim)| {
         claims
             .iter()
@@ -254,13 +270,42 @@ pub fn part_1(input: &Vec<&str>) -> usize {
 /// ### Example
 /// ```
 /// let input = vec![ "#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2" ];
-/// assert_eq!( day_3::part_2( &input ), 3 );
+/// let claims: Vec<day_3::Claim> = input.iter().map( |line| day_3::Claim::parse( line ).unwrap() ).collect();
+/// assert_eq!( day_3::part_2( &claims ).unwrap(), 3 );
 /// ```
-pub fn part_2(input: &Vec<&str>) -> i32 {
-    let claims: Vec<Claim> = input.iter().map(|line| Claim::parse(line)).collect();
+///
+/// ### Errors
+/// Fails if every claim overlaps with another one.
+pub fn part_2(claims: &[Claim]) -> Result<i32, AocError> {
     let result = claims.iter().find(|claim| {
         let mut others = claims.iter().filter(|other| other.id != claim.id);
         others.all(|other| other.overlap(claim).is_empty())
     });
-    result.expect("No intact claim found").id
+    result
+        .map(|claim| claim.id)
+        .ok_or_else(|| AocError::NoSolution("no intact claim found".to_string()))
+}
+
+/// # No Matter How You Slice It
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "No Matter How You Slice It";
+
+    type Input<'a> = Vec<Claim>;
+    type Output1 = usize;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Claim>, AocError> {
+        utils::parse_lines(input, Claim::parse)
+    }
+
+    fn part_1(claims: &Vec<Claim>) -> Result<usize, AocError> {
+        Ok(part_1(claims))
+    }
+
+    fn part_2(claims: &Vec<Claim>) -> Result<i32, AocError> {
+        part_2(claims)
+    }
 }
@@ -2,6 +2,10 @@ extern crate regex;
 
 use regex::Regex;
 use std::collections::HashMap;
+use utils::{AocError, Solution, Span};
+
+/// Minutes asleep, per guard and per minute
+pub type SleepLog = HashMap<u32, HashMap<u8, u32>>;
 
 /// # Accumulate all events in the log per guard and per minute.
 //
@@ -19,23 +23,37 @@ use std::collections::HashMap;
 ///     "[1518-11-03 00:05] falls asleep",
 ///     "[1518-11-03 00:10] wakes up"
 ///   ];
-/// let accumulated_events = day_4::accumulate_events(input);
+/// let accumulated_events = day_4::accumulate_events(&input.join("\n")).unwrap();
 /// let mut first_guard: Vec<(&u8,&u32)> = accumulated_events.get(&1).unwrap().iter().collect();
 ///
 /// first_guard.sort_by_cached_key( |(&min,_)| min );
 /// assert_eq!(first_guard, vec!((&5,&2), (&6,&1), (&7,&1),(&8,&2),(&9,&2)));
 ///
+/// let mut second_guard: Vec<(&u8,&u32)> = accumulated_events.get(&2).unwrap().iter().collect();
 /// second_guard.sort_by_cached_key( |(&min,_)| min );
 /// assert_eq!(second_guard, vec!((&30,&1), (&31,&1)));
+///
+/// let input = vec![
+///     "[1518-11-01 00:05] falls asleep",
+///     "[1518-11-01 00:06] wakes up",
+///   ];
+/// assert!(day_4::accumulate_events(&input.join("\n")).is_err());
 /// ```
-pub fn accumulate_events(mut lines: Vec<&str>) -> HashMap<u32, HashMap<u8, u32>> {
-  lines.sort();
-  let events = lines.iter().map(|line| GuardEvent::parse(line));
+///
+/// ### Errors
+/// Fails if a line can't be parsed, the error then points at that line.  Also
+/// fails if the events are inconsistent, e.g. a guard waking up without having
+/// fallen asleep.
+pub fn accumulate_events(input: &str) -> Result<SleepLog, AocError> {
+  // Keep the original line numbers around for error reporting
+  let mut lines: Vec<(usize, &str)> = input.lines().enumerate().collect();
+  lines.sort_by_key(|&(_, line)| line);
   let mut last_guard = None;
   let mut asleep_since = None;
 
-  let mut accumulator: HashMap<u32, HashMap<u8, u32>> = HashMap::new();
-  for event in events {
+  let mut accumulator: SleepLog = HashMap::new();
+  for (index, line) in lines {
+    let event = GuardEvent::parse(line).map_err(|err| err.within(Span::of(input, line)))?;
     match (event, last_guard, asleep_since) {
       (GuardEvent::ShiftStart(next_guard), _, _) => {
         asleep_since = None;
@@ -43,39 +61,69 @@ pub fn accumulate_events(mut lines: Vec<&str>) -> HashMap<u32, HashMap<u8, u32>>
       }
       (GuardEvent::FallsAsleep(minutes), _, _) => asleep_since = Some(minutes),
       (GuardEvent::WakesUp(minutes), Some(guard), Some(since)) => {
-        let series = accumulator.entry(guard).or_insert_with(HashMap::new);
+        asleep_since = None;
+        let series = accumulator.entry(guard).or_default();
         for minute in since..minutes {
           let count = series.entry(minute).or_insert(0);
           *count += 1;
         }
       }
-      _ => panic!("Inconsistent state reached"),
+      (GuardEvent::WakesUp(_), None, _) => {
+        return Err(AocError::InvalidInput(format!(
+          "line {}: a guard wakes up before any shift started",
+          index + 1
+        )))
+      }
+      (GuardEvent::WakesUp(_), Some(guard), None) => {
+        return Err(AocError::InvalidInput(format!(
+          "line {}: guard #{} wakes up without falling asleep",
+          index + 1,
+          guard
+        )))
+      }
     }
   }
 
-  accumulator
+  Ok(accumulator)
 }
 
-pub fn part_1(lines: Vec<&str>) -> u32 {
-  let accumulated_events = accumulate_events(lines);
-  let (guard_number, minutes) = accumulated_events
+/// The minute a guard was asleep the most, with the number of times
+fn sleepiest_minute(minutes: &HashMap<u8, u32>) -> Option<(u8, u32)> {
+  minutes
+    .iter()
+    .max_by_key(|(_, &count)| count)
+    .map(|(&minute, &count)| (minute, count))
+}
+
+/// ## Part 1
+/// Find the guard that sleeps the most, and the minute that guard is asleep
+/// the most.
+///
+/// ### Errors
+/// Fails if no guard ever fell asleep.
+pub fn part_1(accumulated_events: &SleepLog) -> Result<u32, AocError> {
+  let (guard_number, (chosen_minute, _)) = accumulated_events
     .iter()
-    .max_by_key(|(_, minutes)| minutes.values().fold(0, |sum, count| sum + count))
-    .unwrap();
+    .max_by_key(|(_, minutes)| minutes.values().sum::<u32>())
+    .and_then(|(guard_number, minutes)| Some((guard_number, sleepiest_minute(minutes)?)))
+    .ok_or_else(|| AocError::NoSolution("no guard fell asleep".to_string()))?;
 
-  let chosen_minute = minutes.iter().max_by_key(|(_, &count)| count).unwrap().0;
-  guard_number * u32::from(*chosen_minute)
+  Ok(guard_number * u32::from(chosen_minute))
 }
 
-pub fn part_2(lines: Vec<&str>) -> u32 {
-  let accumulated_events = accumulate_events(lines);
-  let (guard_number, minutes) = accumulated_events
+/// ## Part 2
+/// Find the guard that is most frequently asleep on the same minute.
+///
+/// ### Errors
+/// Fails if no guard ever fell asleep.
+pub fn part_2(accumulated_events: &SleepLog) -> Result<u32, AocError> {
+  let (guard_number, (chosen_minute, _)) = accumulated_events
     .iter()
-    .max_by_key(|(_, minutes)| minutes.iter().max_by_key(|(_, &count)| count).unwrap().1)
-    .unwrap();
+    .filter_map(|(guard_number, minutes)| Some((guard_number, sleepiest_minute(minutes)?)))
+    .max_by_key(|(_, (_, count))| *count)
+    .ok_or_else(|| AocError::NoSolution("no guard fell asleep".to_string()))?;
 
-  let chosen_minute = minutes.iter().max_by_key(|(_, &count)| count).unwrap().0;
-  guard_number * u32::from(*chosen_minute)
+  Ok(guard_number * u32::from(chosen_minute))
 }
 
 #[derive(Debug, PartialEq)]
@@ -91,54 +139,72 @@ impl GuardEvent {
   /// ## Example
   /// ```
   /// let input = "[1518-11-01 00:00] Guard #10 begins shift";
-  /// let event = day_4::GuardEvent::parse( input );
+  /// let event = day_4::GuardEvent::parse( input ).unwrap();
   /// assert_eq!( event, day_4::GuardEvent::ShiftStart( 10 ) );
   ///
   /// let input = "[1518-11-01 00:05] falls asleep";
-  /// let event = day_4::GuardEvent::parse( input );
+  /// let event = day_4::GuardEvent::parse( input ).unwrap();
   /// assert_eq!( event, day_4::GuardEvent::FallsAsleep( 5 ) );
   ///
   /// let input = "[1518-11-01 00:25] wakes up";
-  /// let event = day_4::GuardEvent::parse( input );
+  /// let event = day_4::GuardEvent::parse( input ).unwrap();
   /// assert_eq!( event, day_4::GuardEvent::WakesUp(25) );
+  ///
+  /// let input = "[1518-11-01 00:25] dozes off";
+  /// assert!( day_4::GuardEvent::parse( input ).is_err() );
+  ///
+  /// let input = "[1518-11-01 00:00] Guard #99999999999 begins shift";
+  /// let error = day_4::GuardEvent::parse( input ).unwrap_err();
+  /// assert_eq!( error.to_string(), "guard number out of range (bytes 26..37)" );
   /// ```
-  pub fn parse(input: &str) -> GuardEvent {
+  ///
+  /// ### Errors
+  /// Fails if the line doesn't describe one of the known events.
+  pub fn parse(input: &str) -> Result<GuardEvent, AocError> {
     let guard_regex = Regex::new(r"^.* Guard #([0-9]+) begins shift$").unwrap();
     let asleep_regex = Regex::new(r"^\[[0-9 -]+:([0-9]{2})\] falls asleep$").unwrap();
     let wake_regex = Regex::new(r"^\[[0-9 -]+:([0-9]{2})\] wakes up$").unwrap();
 
-    if guard_regex.is_match(input) {
-      let guard_number = guard_regex
-        .captures(input)
-        .unwrap()
-        .get(1)
-        .unwrap()
-        .as_str()
-        .parse::<u32>()
-        .unwrap();
-      GuardEvent::ShiftStart(guard_number)
-    } else if asleep_regex.is_match(input) {
-      let minutes = asleep_regex
-        .captures(input)
-        .unwrap()
-        .get(1)
-        .unwrap()
-        .as_str()
-        .parse::<u8>()
-        .unwrap();
-      GuardEvent::FallsAsleep(minutes)
-    } else if wake_regex.is_match(input) {
-      let minutes = wake_regex
-        .captures(input)
-        .unwrap()
-        .get(1)
-        .unwrap()
-        .as_str()
-        .parse::<u8>()
-        .unwrap();
-      GuardEvent::WakesUp(minutes)
+    if let Some(captures) = guard_regex.captures(input) {
+      let number = captures.get(1).unwrap();
+      let guard_number = number.as_str().parse::<u32>().map_err(|_| {
+        AocError::parse_at(
+          Span::new(number.start(), number.end()),
+          "guard number out of range",
+        )
+      })?;
+      Ok(GuardEvent::ShiftStart(guard_number))
+    } else if let Some(captures) = asleep_regex.captures(input) {
+      // Two digits always fit in a `u8`
+      Ok(GuardEvent::FallsAsleep(captures[1].parse::<u8>().unwrap()))
+    } else if let Some(captures) = wake_regex.captures(input) {
+      Ok(GuardEvent::WakesUp(captures[1].parse::<u8>().unwrap()))
     } else {
-      panic!("Couldn't parse {}", input)
+      Err(AocError::parse(format!("couldn't parse {:?}", input)))
     }
   }
 }
+
+/// # Repose Record
+pub struct Day4;
+
+impl Solution for Day4 {
+  const DAY: u8 = 4;
+  const TITLE: &'static str = "Repose Record";
+
+  type Input<'a> = SleepLog;
+  type Output1 = u32;
+  type Output2 = u32;
+
+  fn parse(input: &str) -> Result<SleepLog, AocError> {
+    accumulate_events(input)
+  }
+
+  fn part_1(accumulated_events: &SleepLog) -> Result<u32, AocError> {
+    part_1(accumulated_events)
+  }
+
+  fn part_2(accumulated_events: &SleepLog) -> Result<u32, AocError> {
+    part_2(accumulated_events)
+  }
+}
@@ -1,7 +1,229 @@
-pub fn part_1() -> () {
-  unimplemented!("Not ready yet")
+//! # Day 5 of Advent of Code
+//!
+//! `day_5` contains the solution to the puzzle of
+//! [day 5](https://adventofcode.com/2018/day/5) of the 2018 edition of Advent of Code.
+
+use std::io::{ErrorKind, Read};
+use std::thread;
+use utils::{AocError, Solution, Span};
+
+/// ## Check whether two units react
+///
+/// Units react when they are of the same type but of opposite polarity.
+///
+/// ### Example
+/// ```
+/// assert!( day_5::reacts( b'a', b'A' ) );
+/// assert!( day_5::reacts( b'B', b'b' ) );
+/// assert!( !day_5::reacts( b'a', b'a' ) );
+/// assert!( !day_5::reacts( b'a', b'B' ) );
+/// ```
+pub fn reacts(a: u8, b: u8) -> bool {
+  a != b && a.eq_ignore_ascii_case(&b)
+}
+
+/// ## Stack of units that survived the reaction so far
+///
+/// Units are pushed one by one, a unit reacting with the top of the stack
+/// destroys both.  Only the surviving units are kept in memory, so arbitrarily
+/// long polymers can be fed in chunks.
+///
+/// ### Example
+/// ```
+/// let mut reducer = day_5::Reducer::default();
+/// reducer.feed( b"dabAcCa" );
+/// reducer.feed( b"CBAcCcaDA" );
+/// assert_eq!( reducer.len(), 10 );
+/// assert_eq!( reducer.into_units(), b"dabCBAcaDA".to_vec() );
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Reducer {
+  stack: Vec<u8>,
 }
 
-pub fn part_2() -> () {
-  unimplemented!("Not ready yet")
+impl Reducer {
+  pub fn push(&mut self, unit: u8) {
+    match self.stack.last() {
+      Some(&top) if reacts(top, unit) => {
+        self.stack.pop();
+      }
+      _ => self.stack.push(unit),
+    }
+  }
+
+  pub fn feed(&mut self, units: &[u8]) {
+    units.iter().for_each(|&unit| self.push(unit));
+  }
+
+  pub fn len(&self) -> usize {
+    self.stack.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.stack.is_empty()
+  }
+
+  pub fn into_units(self) -> Vec<u8> {
+    self.stack
+  }
+}
+
+/// ## Fully react a polymer
+///
+/// ### Example
+/// ```
+/// assert_eq!( day_5::reduce( "dabAcCaCBAcCcaDA".bytes() ), b"dabCBAcaDA".to_vec() );
+/// assert_eq!( day_5::reduce( "abBA".bytes() ), b"".to_vec() );
+/// ```
+pub fn reduce<I: IntoIterator<Item = u8>>(units: I) -> Vec<u8> {
+  let mut reducer = Reducer::default();
+  units.into_iter().for_each(|unit| reducer.push(unit));
+  reducer.into_units()
+}
+
+/// Size of the chunks read by `reduce_reader`
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// ## Fully react a polymer read from `reader`
+///
+/// The polymer is read in chunks and never held in memory as a whole, only
+/// the units that survive the reaction are.  Trailing whitespace is ignored.
+///
+/// ### Example
+/// ```
+/// let reduced = day_5::reduce_reader( "dabAcCaCBAcCcaDA\n".as_bytes() ).unwrap();
+/// assert_eq!( reduced, b"dabCBAcaDA".to_vec() );
+///
+/// // Reactions across chunk boundaries
+/// let polymer = format!( "x{}{}X", "ab".repeat( 100_000 ), "BA".repeat( 100_000 ) );
+/// assert_eq!( day_5::reduce_reader( polymer.as_bytes() ).unwrap(), b"".to_vec() );
+///
+/// let error = day_5::reduce_reader( "dabA-cC".as_bytes() ).unwrap_err();
+/// assert_eq!( error.to_string(), "unit '-' is not a letter (bytes 4..5)" );
+///
+/// let error = day_5::reduce_reader( "dabA\ncC".as_bytes() ).unwrap_err();
+/// assert_eq!( error.to_string(), "unit 'c' follows the end of the polymer (bytes 5..6)" );
+/// ```
+///
+/// ### Errors
+/// Fails if reading fails or the polymer contains anything but ASCII letters.
+pub fn reduce_reader<R: Read>(mut reader: R) -> Result<Vec<u8>, AocError> {
+  let mut reducer = Reducer::default();
+  let mut chunk = vec![0; CHUNK_SIZE];
+  let mut offset = 0;
+  let mut ended = false;
+
+  loop {
+    let read = match reader.read(&mut chunk) {
+      Ok(0) => return Ok(reducer.into_units()),
+      Ok(read) => read,
+      Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+      Err(err) => return Err(err.into()),
+    };
+
+    for (index, &unit) in chunk[..read].iter().enumerate() {
+      let span = || Span::new(offset + index, offset + index + 1);
+      if unit.is_ascii_whitespace() {
+        ended = true;
+      } else if !unit.is_ascii_alphabetic() {
+        return Err(AocError::parse_at(
+          span(),
+          format!("unit {:?} is not a letter", char::from(unit)),
+        ));
+      } else if ended {
+        return Err(AocError::parse_at(
+          span(),
+          format!("unit {:?} follows the end of the polymer", char::from(unit)),
+        ));
+      } else {
+        reducer.push(unit);
+      }
+    }
+    offset += read;
+  }
+}
+
+/// ## Part 1
+/// Count the units remaining after fully reacting the polymer.
+///
+/// ### Example
+/// ```
+/// assert_eq!( day_5::part_1( "dabAcCaCBAcCcaDA" ), 10 );
+/// assert_eq!( day_5::part_1( "aA" ), 0 );
+/// assert_eq!( day_5::part_1( "abAB" ), 4 );
+/// assert_eq!( day_5::part_1( "aabAAB" ), 6 );
+/// ```
+pub fn part_1(polymer: &str) -> usize {
+  reduce(polymer.bytes()).len()
+}
+
+/// ## Part 2
+/// Find the shortest polymer that can be produced by removing all units of a
+/// single type and fully reacting the result.
+///
+/// ### Example
+/// ```
+/// assert_eq!( day_5::part_2( "dabAcCaCBAcCcaDA" ), 4 );
+/// ```
+pub fn part_2(polymer: &str) -> usize {
+  best_removal(&reduce(polymer.bytes()))
+}
+
+/// ## Shortest polymer after removing a unit type from a reduced polymer
+///
+/// Removing a unit type and reacting gives the same result whether or not
+/// the polymer was reacted before, so this starts from the result of part 1.
+/// The 26 unit types are evaluated in parallel.
+///
+/// ### Example
+/// ```
+/// let reduced = day_5::reduce( "dabAcCaCBAcCcaDA".bytes() );
+/// assert_eq!( day_5::best_removal( &reduced ), 4 );
+/// assert_eq!( day_5::best_removal( b"" ), 0 );
+/// ```
+pub fn best_removal(reduced: &[u8]) -> usize {
+  thread::scope(|scope| {
+    let removals: Vec<_> = (b'a'..=b'z')
+      .map(|removed| {
+        scope.spawn(move || {
+          let units = reduced
+            .iter()
+            .copied()
+            .filter(|unit| unit.to_ascii_lowercase() != removed);
+          reduce(units).len()
+        })
+      })
+      .collect();
+
+    removals
+      .into_iter()
+      .map(|removal| removal.join().expect("a removal thread panicked"))
+      .min()
+      .unwrap_or(0)
+  })
+}
+
+/// # Alchemical Reduction
+pub struct Day5;
+
+impl Solution for Day5 {
+  const DAY: u8 = 5;
+  const TITLE: &'static str = "Alchemical Reduction";
+
+  /// The fully reacted polymer, shared by both parts
+  type Input<'a> = Vec<u8>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Vec<u8>, AocError> {
+    reduce_reader(input.as_bytes())
+  }
+
+  fn part_1(reduced: &Vec<u8>) -> Result<usize, AocError> {
+    Ok(reduced.len())
+  }
+
+  fn part_2(reduced: &Vec<u8>) -> Result<usize, AocError> {
+    Ok(best_removal(reduced))
+  }
 }
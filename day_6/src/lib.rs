@@ -0,0 +1,242 @@
+//! # Day 6 of Advent of Code
+//!
+//! `day_6` contains the solution to the puzzle of
+//! [day 6](https://adventofcode.com/2018/day/6) of the 2018 edition of Advent of Code.
+
+use std::collections::HashSet;
+use utils::{parse_lines, AocError, Solution, Span};
+
+/// Total distance threshold of the region of part 2 in the puzzle
+pub const THRESHOLD: i32 = 10_000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Coordinate {
+  pub x: i32,
+  pub y: i32,
+}
+
+impl Coordinate {
+  pub fn new(x: i32, y: i32) -> Coordinate {
+    Coordinate { x, y }
+  }
+
+  /// ## Parse an input line into a coordinate
+  ///
+  /// ### Example
+  /// ```
+  /// use day_6::Coordinate;
+  ///
+  /// assert_eq!( Coordinate::parse( "1, 6" ).unwrap(), Coordinate::new( 1, 6 ) );
+  /// assert_eq!( Coordinate::parse( "1,6" ).unwrap(), Coordinate::new( 1, 6 ) );
+  ///
+  /// let error = Coordinate::parse( "1, six" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid y coordinate \"six\" (bytes 3..6)" );
+  ///
+  /// assert!( Coordinate::parse( "1 6" ).is_err() );
+  /// ```
+  pub fn parse(line: &str) -> Result<Coordinate, AocError> {
+    let (x, y) = line
+      .split_once(',')
+      .ok_or_else(|| AocError::parse(format!("invalid coordinate \"{}\"", line)))?;
+
+    let number = |field: &str, name: &str| {
+      let field = field.trim();
+      field.parse::<i32>().map_err(|_| {
+        AocError::parse_at(
+          Span::of(line, field),
+          format!("invalid {} coordinate \"{}\"", name, field),
+        )
+      })
+    };
+
+    Ok(Coordinate::new(number(x, "x")?, number(y, "y")?))
+  }
+
+  pub fn distance(&self, x: i32, y: i32) -> i32 {
+    (self.x - x).abs() + (self.y - y).abs()
+  }
+}
+
+/// ## Smallest rectangle containing all coordinates
+///
+/// Returns `(min_x, min_y, max_x, max_y)`, or `None` without coordinates.
+fn bounds(coordinates: &[Coordinate]) -> Option<(i32, i32, i32, i32)> {
+  let first = coordinates.first()?;
+  Some(coordinates.iter().fold(
+    (first.x, first.y, first.x, first.y),
+    |(min_x, min_y, max_x, max_y), c| (min_x.min(c.x), min_y.min(c.y), max_x.max(c.x), max_y.max(c.y)),
+  ))
+}
+
+/// ## Index of the coordinate closest to a location
+///
+/// Returns `None` when several coordinates are equally close.
+///
+/// ### Example
+/// ```
+/// use day_6::Coordinate;
+///
+/// let coordinates = vec![ Coordinate::new( 1, 1 ), Coordinate::new( 5, 1 ) ];
+/// assert_eq!( day_6::closest( &coordinates, 2, 4 ), Some( 0 ) );
+/// assert_eq!( day_6::closest( &coordinates, 3, 4 ), None );
+/// ```
+pub fn closest(coordinates: &[Coordinate], x: i32, y: i32) -> Option<usize> {
+  let mut best = None;
+  let mut best_distance = i32::MAX;
+  let mut tied = false;
+
+  for (index, coordinate) in coordinates.iter().enumerate() {
+    let distance = coordinate.distance(x, y);
+    if distance < best_distance {
+      best = Some(index);
+      best_distance = distance;
+      tied = false;
+    } else if distance == best_distance {
+      tied = true;
+    }
+  }
+
+  if tied {
+    None
+  } else {
+    best
+  }
+}
+
+/// ## Part 1
+/// Find the size of the largest area that isn't infinite.
+///
+/// An area touching the bounding box of the coordinates extends forever, as
+/// every location further out is closer to the same coordinate.
+///
+/// ### Example
+/// ```
+/// let coordinates = day_6::read_coordinates( "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9" ).unwrap();
+/// assert_eq!( day_6::part_1( &coordinates ).unwrap(), 17 );
+/// ```
+///
+/// ### Errors
+/// Fails if every area is infinite.
+pub fn part_1(coordinates: &[Coordinate]) -> Result<usize, AocError> {
+  let (min_x, min_y, max_x, max_y) = bounds(coordinates)
+    .ok_or_else(|| AocError::InvalidInput("there are no coordinates".to_string()))?;
+
+  let mut areas = vec![0; coordinates.len()];
+  let mut infinite = HashSet::new();
+
+  for y in min_y..=max_y {
+    for x in min_x..=max_x {
+      if let Some(owner) = closest(coordinates, x, y) {
+        areas[owner] += 1;
+        if x == min_x || x == max_x || y == min_y || y == max_y {
+          infinite.insert(owner);
+        }
+      }
+    }
+  }
+
+  areas
+    .into_iter()
+    .enumerate()
+    .filter(|(owner, _)| !infinite.contains(owner))
+    .map(|(_, area)| area)
+    .max()
+    .ok_or_else(|| AocError::NoSolution("every area is infinite".to_string()))
+}
+
+/// ## Part 2
+/// Count the locations whose total distance to all coordinates is less than
+/// `threshold`.
+///
+/// The region can extend beyond the bounding box of the coordinates, but
+/// every step away from it adds the number of coordinates to the total
+/// distance, so the search is widened by `threshold / coordinates` only.
+///
+/// ### Example
+/// ```
+/// let coordinates = day_6::read_coordinates( "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9" ).unwrap();
+/// assert_eq!( day_6::part_2( &coordinates, 32 ), 16 );
+/// ```
+pub fn part_2(coordinates: &[Coordinate], threshold: i32) -> usize {
+  let (min_x, min_y, max_x, max_y) = match bounds(coordinates) {
+    Some(bounds) => bounds,
+    None => return 0,
+  };
+  let margin = threshold / coordinates.len() as i32 + 1;
+
+  let total = |x: i32, y: i32| -> i32 { coordinates.iter().map(|c| c.distance(x, y)).sum() };
+
+  (min_y - margin..=max_y + margin)
+    .flat_map(|y| (min_x - margin..=max_x + margin).map(move |x| (x, y)))
+    .filter(|&(x, y)| total(x, y) < threshold)
+    .count()
+}
+
+/// ## Render the areas as in the puzzle text
+///
+/// The locations from `(0, 0)` up to `width` by `height` are shown.
+/// Coordinates are labelled with capitals, the locations closest to them
+/// with the lowercase letter and ties with a dot.  Labels start over after
+/// 26 coordinates.
+///
+/// ### Example
+/// ```
+/// let coordinates = day_6::read_coordinates( "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9" ).unwrap();
+/// assert_eq!( day_6::render( &coordinates, 10, 10 ), "\
+/// aaaaa.cccc
+/// aAaaa.cccc
+/// aaaddecccc
+/// aadddeccCc
+/// ..dDdeeccc
+/// bb.deEeecc
+/// bBb.eeee..
+/// bbb.eeefff
+/// bbb.eeffff
+/// bbb.ffffFf
+/// " );
+/// ```
+pub fn render(coordinates: &[Coordinate], width: i32, height: i32) -> String {
+  let label = |index: usize| (b'a' + (index % 26) as u8) as char;
+
+  let mut map = String::new();
+  for y in 0..height {
+    for x in 0..width {
+      map.push(match closest(coordinates, x, y) {
+        Some(owner) if coordinates[owner].distance(x, y) == 0 => label(owner).to_ascii_uppercase(),
+        Some(owner) => label(owner),
+        None => '.',
+      });
+    }
+    map.push('\n');
+  }
+  map
+}
+
+/// ## Read the coordinates from the puzzle input
+pub fn read_coordinates(input: &str) -> Result<Vec<Coordinate>, AocError> {
+  parse_lines(input, Coordinate::parse)
+}
+
+/// # Chronal Coordinates
+pub struct Day6;
+
+impl Solution for Day6 {
+  const DAY: u8 = 6;
+  const TITLE: &'static str = "Chronal Coordinates";
+
+  type Input<'a> = Vec<Coordinate>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Vec<Coordinate>, AocError> {
+    read_coordinates(input)
+  }
+
+  fn part_1(coordinates: &Vec<Coordinate>) -> Result<usize, AocError> {
+    part_1(coordinates)
+  }
+
+  fn part_2(coordinates: &Vec<Coordinate>) -> Result<usize, AocError> {
+    Ok(part_2(coordinates, THRESHOLD))
+  }
+}
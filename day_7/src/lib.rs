@@ -0,0 +1,307 @@
+//! # Day 7 of Advent of Code
+//!
+//! `day_7` contains the solution to the puzzle of
+//! [day 7](https://adventofcode.com/2018/day/7) of the 2018 edition of Advent of Code.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use utils::{parse_lines, AocError, Solution, Span};
+
+/// Number of workers in the puzzle, including yourself
+pub const WORKERS: usize = 5;
+/// Seconds every step takes on top of its letter in the puzzle
+pub const BASE_DURATION: u32 = 60;
+
+/// ## Step `before` must be finished before step `after` can begin
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Requirement {
+  pub before: char,
+  pub after: char,
+}
+
+impl Requirement {
+  /// ## Parse an input line into a requirement
+  ///
+  /// ### Example
+  /// ```
+  /// use day_7::Requirement;
+  ///
+  /// assert_eq!(
+  ///   Requirement::parse( "Step C must be finished before step A can begin." ).unwrap(),
+  ///   Requirement { before: 'C', after: 'A' }
+  /// );
+  ///
+  /// let error = Requirement::parse( "Step C must be finished before step 7 can begin." ).unwrap_err();
+  /// assert_eq!( error.to_string(), "step \"7\" is not a capital letter (bytes 36..37)" );
+  ///
+  /// assert!( Requirement::parse( "Step C must be done before step A can begin." ).is_err() );
+  /// ```
+  pub fn parse(line: &str) -> Result<Requirement, AocError> {
+    let invalid = || AocError::parse(format!("invalid requirement \"{}\"", line));
+
+    let rest = line.strip_prefix("Step ").ok_or_else(invalid)?;
+    let (before, rest) = rest
+      .split_once(" must be finished before step ")
+      .ok_or_else(invalid)?;
+    let after = rest.strip_suffix(" can begin.").ok_or_else(invalid)?;
+
+    let step = |field: &str| {
+      let mut chars = field.chars();
+      match (chars.next(), chars.next()) {
+        (Some(step), None) if step.is_ascii_uppercase() => Ok(step),
+        _ => Err(AocError::parse_at(
+          Span::of(line, field),
+          format!("step \"{}\" is not a capital letter", field),
+        )),
+      }
+    };
+
+    Ok(Requirement {
+      before: step(before)?,
+      after: step(after)?,
+    })
+  }
+}
+
+/// ## Prerequisites of every step
+pub type Steps = BTreeMap<char, BTreeSet<char>>;
+
+/// ## Collect the prerequisites of every step mentioned in the requirements
+///
+/// ### Example
+/// ```
+/// use day_7::Requirement;
+///
+/// let steps = day_7::steps( &[ Requirement { before: 'C', after: 'A' } ] );
+/// assert!( steps[&'C'].is_empty() );
+/// assert!( steps[&'A'].contains( &'C' ) );
+/// ```
+pub fn steps(requirements: &[Requirement]) -> Steps {
+  let mut steps = Steps::new();
+  for requirement in requirements {
+    steps.entry(requirement.before).or_default();
+    steps
+      .entry(requirement.after)
+      .or_default()
+      .insert(requirement.before);
+  }
+  steps
+}
+
+/// First step, alphabetically, that isn't started and whose prerequisites are done
+fn next_available(steps: &Steps, started: &BTreeSet<char>, done: &BTreeSet<char>) -> Option<char> {
+  steps
+    .iter()
+    .find(|(step, prerequisites)| !started.contains(step) && prerequisites.is_subset(done))
+    .map(|(step, _)| *step)
+}
+
+fn cycle() -> AocError {
+  AocError::NoSolution("the requirements contain a cycle".to_string())
+}
+
+/// ## Part 1
+/// Find the order in which the steps are completed, taking the first step
+/// alphabetically when several are available.
+///
+/// ### Example
+/// ```
+/// let requirements = day_7::read_requirements( "\
+/// Step C must be finished before step A can begin.
+/// Step C must be finished before step F can begin.
+/// Step A must be finished before step B can begin.
+/// Step A must be finished before step D can begin.
+/// Step B must be finished before step E can begin.
+/// Step D must be finished before step E can begin.
+/// Step F must be finished before step E can begin." ).unwrap();
+/// let steps = day_7::steps( &requirements );
+/// assert_eq!( day_7::part_1( &steps ).unwrap(), "CABDFE" );
+/// ```
+///
+/// ### Errors
+/// Fails if the requirements contain a cycle.
+pub fn part_1(steps: &Steps) -> Result<String, AocError> {
+  let mut done = BTreeSet::new();
+  let mut order = String::new();
+
+  while order.len() < steps.len() {
+    let step = next_available(steps, &done, &done).ok_or_else(cycle)?;
+    done.insert(step);
+    order.push(step);
+  }
+  Ok(order)
+}
+
+/// ## Part 2
+/// Find how long it takes `workers` to complete all steps, when a step takes
+/// `base_duration` seconds plus its position in the alphabet.
+///
+/// ### Example
+/// ```
+/// let requirements = day_7::read_requirements( "\
+/// Step C must be finished before step A can begin.
+/// Step C must be finished before step F can begin.
+/// Step A must be finished before step B can begin.
+/// Step A must be finished before step D can begin.
+/// Step B must be finished before step E can begin.
+/// Step D must be finished before step E can begin.
+/// Step F must be finished before step E can begin." ).unwrap();
+/// let steps = day_7::steps( &requirements );
+/// assert_eq!( day_7::part_2( &steps, 2, 0 ).unwrap(), 15 );
+/// ```
+///
+/// ### Errors
+/// Fails if the requirements contain a cycle or there are no workers.
+pub fn part_2(steps: &Steps, workers: usize, base_duration: u32) -> Result<u32, AocError> {
+  Schedule::new(steps, workers, base_duration).map(|schedule| schedule.duration)
+}
+
+/// ## What every worker is doing during a second
+#[derive(Debug, PartialEq, Clone)]
+pub struct Second {
+  pub second: u32,
+  /// Step each worker is working on, `None` when idle
+  pub workers: Vec<Option<char>>,
+  /// Steps completed so far, in order
+  pub done: String,
+}
+
+/// ## Schedule of the workers completing all steps
+#[derive(Debug, PartialEq, Clone)]
+pub struct Schedule {
+  pub seconds: Vec<Second>,
+  /// Seconds until all steps are completed
+  pub duration: u32,
+}
+
+impl Schedule {
+  /// ## Simulate the workers second by second
+  ///
+  /// Available steps are handed to idle workers alphabetically.
+  ///
+  /// ### Errors
+  /// Fails if the requirements contain a cycle or there are no workers.
+  pub fn new(steps: &Steps, workers: usize, base_duration: u32) -> Result<Schedule, AocError> {
+    if workers == 0 {
+      return Err(AocError::InvalidInput("there are no workers".to_string()));
+    }
+
+    let duration = |step: char| base_duration + u32::from(step as u8 - b'A') + 1;
+
+    // Step and second it is finished, for every worker
+    let mut busy: Vec<Option<(char, u32)>> = vec![None; workers];
+    let mut started = BTreeSet::new();
+    let mut done = BTreeSet::new();
+    let mut order = String::new();
+    let mut seconds = Vec::new();
+    let mut second = 0;
+
+    loop {
+      for worker in busy.iter_mut() {
+        if let Some((step, finished)) = *worker {
+          if finished == second {
+            done.insert(step);
+            order.push(step);
+            *worker = None;
+          }
+        }
+      }
+
+      for worker in busy.iter_mut().filter(|worker| worker.is_none()) {
+        match next_available(steps, &started, &done) {
+          Some(step) => {
+            started.insert(step);
+            *worker = Some((step, second + duration(step)));
+          }
+          None => break,
+        }
+      }
+
+      seconds.push(Second {
+        second,
+        workers: busy.iter().map(|worker| worker.map(|(step, _)| step)).collect(),
+        done: order.clone(),
+      });
+
+      if done.len() == steps.len() {
+        return Ok(Schedule {
+          seconds,
+          duration: second,
+        });
+      }
+      if busy.iter().all(Option::is_none) {
+        return Err(cycle());
+      }
+      second += 1;
+    }
+  }
+
+  /// ## Render the schedule as a table like the one in the puzzle
+  ///
+  /// ### Example
+  /// ```
+  /// let requirements = day_7::read_requirements( "\
+  /// Step C must be finished before step A can begin.
+  /// Step A must be finished before step B can begin." ).unwrap();
+  /// let schedule = day_7::Schedule::new( &day_7::steps( &requirements ), 2, 0 ).unwrap();
+  /// assert_eq!( schedule.table(), "\
+  /// Second   Worker 1   Worker 2   Done
+  ///    0        C          .
+  ///    1        C          .
+  ///    2        C          .
+  ///    3        A          .       C
+  ///    4        B          .       CA
+  ///    5        B          .       CA
+  ///    6        .          .       CAB
+  /// " );
+  /// ```
+  pub fn table(&self) -> String {
+    let workers = self.seconds.first().map_or(0, |second| second.workers.len());
+
+    let mut table = String::from("Second");
+    for worker in 1..=workers {
+      write!(table, "   Worker {}", worker).unwrap();
+    }
+    table.push_str("   Done\n");
+
+    for second in &self.seconds {
+      let mut row = format!("{:>4}  ", second.second);
+      for step in &second.workers {
+        write!(row, "{:>7}    ", step.unwrap_or('.')).unwrap();
+      }
+      write!(row, "   {}", second.done).unwrap();
+      table.push_str(row.trim_end());
+      table.push('\n');
+    }
+    table
+  }
+}
+
+/// ## Read the requirements from the puzzle input
+pub fn read_requirements(input: &str) -> Result<Vec<Requirement>, AocError> {
+  parse_lines(input, Requirement::parse)
+}
+
+/// # The Sum of Its Parts
+pub struct Day7;
+
+impl Solution for Day7 {
+  const DAY: u8 = 7;
+  const TITLE: &'static str = "The Sum of Its Parts";
+
+  type Input<'a> = Steps;
+  type Output1 = String;
+  type Output2 = u32;
+
+  fn parse(input: &str) -> Result<Steps, AocError> {
+    read_requirements(input).map(|requirements| steps(&requirements))
+  }
+
+  fn part_1(steps: &Steps) -> Result<String, AocError> {
+    part_1(steps)
+  }
+
+  fn part_2(steps: &Steps) -> Result<u32, AocError> {
+    part_2(steps, WORKERS, BASE_DURATION)
+  }
+}
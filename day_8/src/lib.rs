@@ -0,0 +1,224 @@
+//! # Day 8 of Advent of Code
+//!
+//! `day_8` contains the solution to the puzzle of
+//! [day 8](https://adventofcode.com/2018/day/8) of the 2018 edition of Advent of Code.
+//!
+//! Trees in the input can be arbitrarily deep, so nothing in here recurses:
+//! parsing, traversing and even dropping a tree use an explicit stack.
+
+use std::mem;
+use utils::{AocError, Solution, Span};
+
+/// ## A node of the license tree
+///
+/// The derived comparison and debug output do recurse, they are meant for
+/// small trees in tests.
+#[derive(Debug, PartialEq, Default)]
+pub struct Node {
+  pub children: Vec<Node>,
+  pub metadata: Vec<u32>,
+}
+
+/// A node whose children are being parsed
+struct Partial {
+  children_left: usize,
+  metadata: usize,
+  children: Vec<Node>,
+}
+
+/// Next number of the input
+fn number<'a, I: Iterator<Item = &'a str>>(input: &str, fields: &mut I) -> Result<u32, AocError> {
+  let field = fields.next().ok_or_else(|| {
+    let end = input.trim_end().len();
+    AocError::parse_at(Span::new(end, end), "unexpected end of the tree")
+  })?;
+  field.parse().map_err(|_| {
+    AocError::parse_at(
+      Span::of(input, field),
+      format!("invalid number \"{}\"", field),
+    )
+  })
+}
+
+/// Header of a node, the number of children and of metadata entries
+fn header<'a, I: Iterator<Item = &'a str>>(input: &str, fields: &mut I) -> Result<Partial, AocError> {
+  Ok(Partial {
+    children_left: number(input, fields)? as usize,
+    metadata: number(input, fields)? as usize,
+    children: Vec::new(),
+  })
+}
+
+impl Node {
+  pub fn new(children: Vec<Node>, metadata: Vec<u32>) -> Node {
+    Node { children, metadata }
+  }
+
+  /// ## Parse the numbers of the puzzle input into a tree
+  ///
+  /// ### Example
+  /// ```
+  /// use day_8::Node;
+  ///
+  /// let tree = Node::parse( "1 1 0 1 99 2" ).unwrap();
+  /// assert_eq!( tree, Node::new( vec![ Node::new( vec![], vec![ 99 ] ) ], vec![ 2 ] ) );
+  ///
+  /// let error = Node::parse( "1 1 0 x 99 2" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid number \"x\" (bytes 6..7)" );
+  ///
+  /// let error = Node::parse( "1 1 0 1 99" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "unexpected end of the tree (bytes 10..10)" );
+  ///
+  /// let error = Node::parse( "0 1 99 2" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "number after the end of the tree (bytes 7..8)" );
+  /// ```
+  ///
+  /// Deep trees don't overflow the stack:
+  /// ```
+  /// let depth = 100_000;
+  /// let input = format!( "{}0 1 1{}", "1 1 ".repeat( depth ), " 1".repeat( depth ) );
+  /// let tree = day_8::Node::parse( &input ).unwrap();
+  /// assert_eq!( day_8::part_1( &tree ), depth as u32 + 1 );
+  /// assert_eq!( day_8::part_2( &tree ), 1 );
+  /// ```
+  pub fn parse(input: &str) -> Result<Node, AocError> {
+    let mut fields = input.split_ascii_whitespace();
+
+    let mut stack = vec![header(input, &mut fields)?];
+    loop {
+      let top = stack.last_mut().expect("the stack holds the node being parsed");
+      if top.children_left > 0 {
+        let child = header(input, &mut fields)?;
+        stack.push(child);
+        continue;
+      }
+
+      let partial = stack.pop().expect("the stack holds the node being parsed");
+      let metadata = (0..partial.metadata)
+        .map(|_| number(input, &mut fields))
+        .collect::<Result<Vec<u32>, AocError>>()?;
+      let node = Node::new(partial.children, metadata);
+
+      match stack.last_mut() {
+        Some(parent) => {
+          parent.children.push(node);
+          parent.children_left -= 1;
+        }
+        None => {
+          return match fields.next() {
+            Some(field) => Err(AocError::parse_at(
+              Span::of(input, field),
+              "number after the end of the tree",
+            )),
+            None => Ok(node),
+          };
+        }
+      }
+    }
+  }
+
+  /// ## Number of values in the input describing this tree
+  ///
+  /// ### Example
+  /// ```
+  /// let tree = day_8::Node::parse( "1 1 0 1 99 2" ).unwrap();
+  /// assert_eq!( tree.size(), 6 );
+  /// ```
+  pub fn size(&self) -> usize {
+    self.nodes().map(|node| 2 + node.metadata.len()).sum()
+  }
+
+  /// ## All nodes of the tree, parents before their children
+  pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+    let mut stack = vec![self];
+    std::iter::from_fn(move || {
+      let node = stack.pop()?;
+      stack.extend(node.children.iter().rev());
+      Some(node)
+    })
+  }
+}
+
+impl Drop for Node {
+  /// Dismantle the tree one level at a time, the default drop recurses
+  fn drop(&mut self) {
+    let mut stack = mem::take(&mut self.children);
+    while let Some(mut node) = stack.pop() {
+      stack.append(&mut node.children);
+    }
+  }
+}
+
+/// ## Part 1
+/// Sum the metadata of all nodes.
+///
+/// ### Example
+/// ```
+/// let tree = day_8::Node::parse( "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2" ).unwrap();
+/// assert_eq!( day_8::part_1( &tree ), 138 );
+/// ```
+pub fn part_1(tree: &Node) -> u32 {
+  tree.nodes().flat_map(|node| node.metadata.iter()).sum()
+}
+
+/// ## Part 2
+/// Find the value of the root node.
+///
+/// The value of a node without children is the sum of its metadata.
+/// Otherwise its metadata are 1-based indices of children, and its value is
+/// the sum of their values.
+///
+/// ### Example
+/// ```
+/// let tree = day_8::Node::parse( "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2" ).unwrap();
+/// assert_eq!( day_8::part_2( &tree ), 66 );
+/// ```
+pub fn part_2(tree: &Node) -> u32 {
+  // Nodes are visited twice: first to push their children, then to combine
+  // the values of those children, which by then are on top of `values`
+  let mut stack = vec![(tree, false)];
+  let mut values: Vec<u32> = Vec::new();
+
+  while let Some((node, visited)) = stack.pop() {
+    if node.children.is_empty() {
+      values.push(node.metadata.iter().sum());
+    } else if !visited {
+      stack.push((node, true));
+      stack.extend(node.children.iter().rev().map(|child| (child, false)));
+    } else {
+      let children = values.split_off(values.len() - node.children.len());
+      let value = node
+        .metadata
+        .iter()
+        .filter_map(|&index| children.get((index as usize).checked_sub(1)?))
+        .sum();
+      values.push(value);
+    }
+  }
+
+  values.pop().unwrap_or(0)
+}
+
+/// # Memory Maneuver
+pub struct Day8;
+
+impl Solution for Day8 {
+  const DAY: u8 = 8;
+  const TITLE: &'static str = "Memory Maneuver";
+
+  type Input<'a> = Node;
+  type Output1 = u32;
+  type Output2 = u32;
+
+  fn parse(input: &str) -> Result<Node, AocError> {
+    Node::parse(input)
+  }
+
+  fn part_1(tree: &Node) -> Result<u32, AocError> {
+    Ok(part_1(tree))
+  }
+
+  fn part_2(tree: &Node) -> Result<u32, AocError> {
+    Ok(part_2(tree))
+  }
+}
@@ -0,0 +1,267 @@
+//! # Day 9 of Advent of Code
+//!
+//! `day_9` contains the solution to the puzzle of
+//! [day 9](https://adventofcode.com/2018/day/9) of the 2018 edition of Advent of Code.
+
+use std::fmt;
+use utils::{AocError, Solution, Span};
+
+/// Largest last marble a game is played with, the circle takes 8 bytes per marble
+pub const MAX_MARBLES: u32 = 20_000_000;
+
+/// ## Number of players and value of the last marble
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Rules {
+  pub players: usize,
+  pub last_marble: u32,
+}
+
+impl Rules {
+  /// ## Parse the puzzle input into the rules of a game
+  ///
+  /// ### Example
+  /// ```
+  /// use day_9::Rules;
+  ///
+  /// assert_eq!(
+  ///   Rules::parse( "10 players; last marble is worth 1618 points\n" ).unwrap(),
+  ///   Rules { players: 10, last_marble: 1618 }
+  /// );
+  ///
+  /// let error = Rules::parse( "ten players; last marble is worth 1618 points" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid number of players \"ten\" (bytes 0..3)" );
+  ///
+  /// assert!( Rules::parse( "10 players; last marble is 1618" ).is_err() );
+  /// ```
+  pub fn parse(input: &str) -> Result<Rules, AocError> {
+    let line = input.trim();
+    let invalid = || AocError::parse(format!("invalid game \"{}\"", line));
+
+    let (players, rest) = line.split_once(" players; last marble is worth ").ok_or_else(invalid)?;
+    let last_marble = rest.strip_suffix(" points").ok_or_else(invalid)?;
+
+    let number = |field: &str, name: &str| {
+      field.parse().map_err(|_| {
+        AocError::parse_at(
+          Span::of(input, field),
+          format!("invalid {} \"{}\"", name, field),
+        )
+      })
+    };
+
+    Ok(Rules {
+      players: number(players, "number of players")? as usize,
+      last_marble: number(last_marble, "last marble")?,
+    })
+  }
+}
+
+/// ## A game of marbles, played one turn at a time
+///
+/// The circle is a doubly-linked list in an arena indexed by marble, so
+/// placing and removing marbles takes constant time.
+///
+/// ### Example
+/// ```
+/// let mut game = day_9::Game::new( 9, 25 ).unwrap();
+/// assert_eq!( game.to_string(), "[-] (0)" );
+///
+/// game.step();
+/// game.step();
+/// game.step();
+/// assert_eq!( game.to_string(), "[3]  0  2  1 (3)" );
+///
+/// while game.step().is_some() {}
+/// assert_eq!( game.scores()[4], 32 );
+///
+/// assert!( day_9::Game::new( 9, day_9::MAX_MARBLES + 1 ).is_err() );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Game {
+  /// Clockwise neighbour of every marble
+  next: Vec<u32>,
+  /// Counter-clockwise neighbour of every marble
+  prev: Vec<u32>,
+  current: u32,
+  last_marble: u32,
+  /// Marble that is placed next
+  marble: u32,
+  scores: Vec<u64>,
+}
+
+impl Game {
+  /// ### Errors
+  /// Fails if there are no players or the last marble is above `MAX_MARBLES`.
+  pub fn new(players: usize, last_marble: u32) -> Result<Game, AocError> {
+    if players == 0 {
+      return Err(AocError::InvalidInput("there are no players".to_string()));
+    }
+    if last_marble > MAX_MARBLES {
+      return Err(AocError::InvalidInput(format!(
+        "the last marble is above {}",
+        MAX_MARBLES
+      )));
+    }
+
+    let marbles = last_marble as usize + 1;
+    Ok(Game {
+      next: vec![0; marbles],
+      prev: vec![0; marbles],
+      current: 0,
+      last_marble,
+      marble: 1,
+      scores: vec![0; players],
+    })
+  }
+
+  /// ## Play the next turn
+  ///
+  /// Returns the points scored in this turn, or `None` once the last marble
+  /// has been played.
+  pub fn step(&mut self) -> Option<u64> {
+    if self.marble > self.last_marble {
+      return None;
+    }
+    let marble = self.marble;
+    self.marble += 1;
+
+    if marble.is_multiple_of(23) {
+      let removed = (0..7).fold(self.current, |marble, _| self.prev[marble as usize]);
+      let (before, after) = (self.prev[removed as usize], self.next[removed as usize]);
+      self.next[before as usize] = after;
+      self.prev[after as usize] = before;
+      self.current = after;
+
+      let points = u64::from(marble) + u64::from(removed);
+      let player = self.player();
+      self.scores[player] += points;
+      Some(points)
+    } else {
+      let before = self.next[self.current as usize];
+      let after = self.next[before as usize];
+      self.next[before as usize] = marble;
+      self.prev[marble as usize] = before;
+      self.next[marble as usize] = after;
+      self.prev[after as usize] = marble;
+      self.current = marble;
+      Some(0)
+    }
+  }
+
+  /// Index of the player who played the last turn
+  fn player(&self) -> usize {
+    (self.marble as usize - 2) % self.scores.len()
+  }
+
+  pub fn scores(&self) -> &[u64] {
+    &self.scores
+  }
+
+  /// ## Marbles in the circle, clockwise from marble 0
+  pub fn circle(&self) -> Vec<u32> {
+    let mut circle = vec![0];
+    let mut marble = self.next[0];
+    while marble != 0 {
+      circle.push(marble);
+      marble = self.next[marble as usize];
+    }
+    circle
+  }
+
+  /// ## Play all turns and return the highest score
+  pub fn play(mut self) -> u64 {
+    while self.step().is_some() {}
+    self.scores.into_iter().max().unwrap_or(0)
+  }
+}
+
+impl fmt::Display for Game {
+  /// The circle as in the puzzle text, the current marble between parentheses
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let width = (self.marble - 1).to_string().len();
+    let mut line = if self.marble == 1 {
+      "[-] ".to_string()
+    } else {
+      format!("[{}] ", self.player() + 1)
+    };
+
+    for marble in self.circle() {
+      if marble == self.current {
+        line.push_str(&format!("({:>width$})", marble, width = width));
+      } else {
+        line.push_str(&format!(" {:>width$} ", marble, width = width));
+      }
+    }
+    write!(f, "{}", line.trim_end())
+  }
+}
+
+/// ## Part 1
+/// Find the winning score.
+///
+/// ### Example
+/// ```
+/// use day_9::Rules;
+///
+/// assert_eq!( day_9::part_1( &Rules { players: 10, last_marble: 1618 } ).unwrap(), 8317 );
+/// assert_eq!( day_9::part_1( &Rules { players: 13, last_marble: 7999 } ).unwrap(), 146373 );
+/// assert_eq!( day_9::part_1( &Rules { players: 17, last_marble: 1104 } ).unwrap(), 2764 );
+/// assert_eq!( day_9::part_1( &Rules { players: 21, last_marble: 6111 } ).unwrap(), 54718 );
+/// assert_eq!( day_9::part_1( &Rules { players: 30, last_marble: 5807 } ).unwrap(), 37305 );
+/// ```
+///
+/// ### Errors
+/// Fails if there are no players or the last marble is above `MAX_MARBLES`.
+pub fn part_1(rules: &Rules) -> Result<u64, AocError> {
+  Game::new(rules.players, rules.last_marble).map(Game::play)
+}
+
+/// ## Part 2
+/// Find the winning score when the last marble is worth 100 times more.
+///
+/// ### Example
+/// ```
+/// use day_9::Rules;
+///
+/// assert_eq!( day_9::part_2( &Rules { players: 10, last_marble: 1618 } ).unwrap(), 74765078 );
+///
+/// let error = day_9::part_2( &Rules { players: 10, last_marble: u32::MAX } ).unwrap_err();
+/// assert_eq!( error.to_string(), "invalid input: the last marble is too large" );
+/// let error = day_9::part_2( &Rules { players: 10, last_marble: 40_000_000 } ).unwrap_err();
+/// assert_eq!( error.to_string(), "invalid input: the last marble is above 20000000" );
+/// ```
+///
+/// ### Errors
+/// Fails if there are no players or 100 times the last marble is above
+/// `MAX_MARBLES`.
+pub fn part_2(rules: &Rules) -> Result<u64, AocError> {
+  let last_marble = rules
+    .last_marble
+    .checked_mul(100)
+    .ok_or_else(|| AocError::InvalidInput("the last marble is too large".to_string()))?;
+  Game::new(rules.players, last_marble).map(Game::play)
+}
+
+/// # Marble Mania
+pub struct Day9;
+
+impl Solution for Day9 {
+  const DAY: u8 = 9;
+  const TITLE: &'static str = "Marble Mania";
+
+  type Input<'a> = Rules;
+  type Output1 = u64;
+  type Output2 = u64;
+
+  fn parse(input: &str) -> Result<Rules, AocError> {
+    Rules::parse(input)
+  }
+
+  fn part_1(rules: &Rules) -> Result<u64, AocError> {
+    part_1(rules)
+  }
+
+  fn part_2(rules: &Rules) -> Result<u64, AocError> {
+    part_2(rules)
+  }
+}
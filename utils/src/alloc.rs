@@ -0,0 +1,141 @@
+//! # Memory accounting
+//!
+//! `CountingAllocator` wraps the system allocator and keeps track of the
+//! number of allocations, the bytes allocated and the peak memory in use.  It
+//! only counts when a binary installs it as its global allocator:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: utils::alloc::CountingAllocator = utils::alloc::CountingAllocator;
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static TOTAL: AtomicUsize = AtomicUsize::new(0);
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// ## The system allocator, counting what passes through it
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+  fn allocated(size: usize) {
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+    TOTAL.fetch_add(size, Ordering::Relaxed);
+    COUNT.fetch_add(1, Ordering::Relaxed);
+  }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    let ptr = System.alloc(layout);
+    if !ptr.is_null() {
+      CountingAllocator::allocated(layout.size());
+    }
+    ptr
+  }
+
+  unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+    let ptr = System.alloc_zeroed(layout);
+    if !ptr.is_null() {
+      CountingAllocator::allocated(layout.size());
+    }
+    ptr
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout);
+    CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    let new_ptr = System.realloc(ptr, layout, new_size);
+    if !new_ptr.is_null() {
+      CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+      CountingAllocator::allocated(new_size);
+    }
+    new_ptr
+  }
+}
+
+/// ## Whether the counting allocator is installed
+pub fn is_counting() -> bool {
+  COUNT.load(Ordering::Relaxed) > 0
+}
+
+/// ## Memory used while running a piece of code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+  /// Highest number of bytes in use on top of what was in use at the start
+  pub peak_bytes: usize,
+  /// Sum of the sizes of all allocations
+  pub total_bytes: usize,
+  pub allocations: usize,
+}
+
+impl fmt::Display for AllocStats {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "peak {}, {} allocations totalling {}",
+      format_bytes(self.peak_bytes),
+      self.allocations,
+      format_bytes(self.total_bytes)
+    )
+  }
+}
+
+/// ## Run `f` and report the memory it used
+///
+/// All stats are zero when the counting allocator isn't installed.  Memory
+/// used by other threads in the meantime is counted as well.
+///
+/// ### Example
+/// ```
+/// let (value, stats) = utils::alloc::measure(|| vec![1u8; 1024].len());
+/// assert_eq!(value, 1024);
+/// // This doctest doesn't install the counting allocator
+/// assert_eq!(stats, utils::alloc::AllocStats::default());
+/// ```
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, AllocStats) {
+  let start = CURRENT.load(Ordering::Relaxed);
+  PEAK.store(start, Ordering::Relaxed);
+  let total = TOTAL.load(Ordering::Relaxed);
+  let count = COUNT.load(Ordering::Relaxed);
+
+  let value = f();
+
+  let stats = AllocStats {
+    peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(start),
+    total_bytes: TOTAL.load(Ordering::Relaxed) - total,
+    allocations: COUNT.load(Ordering::Relaxed) - count,
+  };
+  (value, stats)
+}
+
+/// ## Human readable size with a binary unit
+///
+/// ### Example
+/// ```
+/// assert_eq!( utils::alloc::format_bytes( 512 ), "512 B" );
+/// assert_eq!( utils::alloc::format_bytes( 3 * 1024 + 512 ), "3.50 KiB" );
+/// assert_eq!( utils::alloc::format_bytes( 5 * 1024 * 1024 ), "5.00 MiB" );
+/// ```
+pub fn format_bytes(bytes: usize) -> String {
+  const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+  if bytes < 1024 {
+    return format!("{} B", bytes);
+  }
+  let mut size = bytes as f64 / 1024.0;
+  let mut unit = 0;
+  while size >= 1024.0 && unit < UNITS.len() - 1 {
+    size /= 1024.0;
+    unit += 1;
+  }
+  format!("{:.2} {}", size, UNITS[unit])
+}
@@ -0,0 +1,157 @@
+use crate::{AocError, Part, Span};
+use std::fmt;
+
+/// # Expected answers for a puzzle input
+///
+/// Answers are written one per line as `part_1: <answer>` and
+/// `part_2: <answer>`.  Empty lines and lines starting with `#` are ignored,
+/// a part without a line has no known answer.
+///
+/// ### Example
+/// ```
+/// use utils::{Answers, Part};
+///
+/// let answers = Answers::parse( "# Verified\npart_1: 556\n" ).unwrap();
+/// assert_eq!( answers.get( Part::One ), Some( "556" ) );
+/// assert_eq!( answers.get( Part::Two ), None );
+///
+/// assert!( Answers::parse( "part_3: 1" ).is_err() );
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+  pub part_1: Option<String>,
+  pub part_2: Option<String>,
+}
+
+impl Answers {
+  pub fn parse(input: &str) -> Result<Answers, AocError> {
+    let mut answers = Answers::default();
+
+    for line in input.lines() {
+      let trimmed = line.trim();
+      if trimmed.is_empty() || trimmed.starts_with('#') {
+        continue;
+      }
+
+      let (key, value) = match trimmed.find(':') {
+        Some(index) => (trimmed[..index].trim(), trimmed[index + 1..].trim()),
+        None => {
+          return Err(AocError::parse_at(
+            Span::of(input, line),
+            "expected `part_1: <answer>` or `part_2: <answer>`",
+          ))
+        }
+      };
+
+      match key {
+        "part_1" => answers.part_1 = Some(value.to_string()),
+        "part_2" => answers.part_2 = Some(value.to_string()),
+        _ => {
+          return Err(AocError::parse_at(
+            Span::of(input, key),
+            format!("unknown part {:?}", key),
+          ))
+        }
+      }
+    }
+
+    Ok(answers)
+  }
+
+  pub fn get(&self, part: Part) -> Option<&str> {
+    match part {
+      Part::One => self.part_1.as_deref(),
+      Part::Two => self.part_2.as_deref(),
+    }
+  }
+
+  /// ## Compare the outcome of a part with its expected answer
+  ///
+  /// ### Example
+  /// ```
+  /// use utils::{Answers, Part, Verdict};
+  ///
+  /// let answers = Answers::parse( "part_1: 556" ).unwrap();
+  /// assert_eq!( answers.verdict( Part::One, Ok( "556".to_string() ) ), Verdict::Pass( "556".to_string() ) );
+  /// assert!( answers.verdict( Part::One, Ok( "448".to_string() ) ).is_failure() );
+  /// assert!( !answers.verdict( Part::Two, Ok( "448".to_string() ) ).is_failure() );
+  /// ```
+  pub fn verdict(&self, part: Part, actual: Result<String, AocError>) -> Verdict {
+    match (self.get(part), actual) {
+      (_, Err(err)) => Verdict::Error(err.to_string()),
+      (None, Ok(actual)) => Verdict::Missing(actual),
+      (Some(expected), Ok(actual)) if expected == actual => Verdict::Pass(actual),
+      (Some(expected), Ok(actual)) => Verdict::Fail {
+        expected: expected.to_string(),
+        actual,
+      },
+    }
+  }
+}
+
+/// # Split an example file into its expected answers and its input
+///
+/// Example files start with their answers, followed by a `---` line and the
+/// puzzle input.
+///
+/// ### Example
+/// ```
+/// use utils::Part;
+///
+/// let (answers, input) = utils::split_example( "part_1: 3\n---\n+1\n+2\n" ).unwrap();
+/// assert_eq!( answers.get( Part::One ), Some( "3" ) );
+/// assert_eq!( answers.get( Part::Two ), None );
+/// assert_eq!( input, "+1\n+2\n" );
+///
+/// assert!( utils::split_example( "+1\n+2\n" ).is_err() );
+/// ```
+///
+/// ### Errors
+/// Fails if there is no separator line or the answers can't be parsed.
+pub fn split_example(example: &str) -> Result<(Answers, &str), AocError> {
+  let separator = example
+    .lines()
+    .find(|line| line.trim_end() == "---")
+    .ok_or_else(|| AocError::parse("expected a `---` line between the answers and the input"))?;
+  let span = Span::of(example, separator);
+
+  let answers = Answers::parse(&example[..span.start])?;
+  let input = example[span.end..]
+    .strip_prefix("\r\n")
+    .or_else(|| example[span.end..].strip_prefix('\n'))
+    .unwrap_or(&example[span.end..]);
+  Ok((answers, input))
+}
+
+/// # Outcome of checking an answer
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+  Pass(String),
+  Fail { expected: String, actual: String },
+  /// There is no expected answer to compare with
+  Missing(String),
+  /// The part failed to produce an answer
+  Error(String),
+}
+
+impl Verdict {
+  pub fn is_failure(&self) -> bool {
+    match self {
+      Verdict::Fail { .. } | Verdict::Error(_) => true,
+      Verdict::Pass(_) | Verdict::Missing(_) => false,
+    }
+  }
+}
+
+impl fmt::Display for Verdict {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Verdict::Pass(actual) => write!(f, "pass ({})", actual),
+      Verdict::Fail { expected, actual } => {
+        write!(f, "FAIL (expected {}, got {})", expected, actual)
+      }
+      Verdict::Missing(actual) => write!(f, "missing (got {})", actual),
+      Verdict::Error(message) => write!(f, "ERROR ({})", message),
+    }
+  }
+}
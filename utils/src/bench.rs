@@ -0,0 +1,272 @@
+//! # Benchmarking of solutions
+//!
+//! Parsing and each of the parts are measured separately, by running them
+//! repeatedly and summarizing the samples.
+
+use crate::alloc::{self, AllocStats};
+use crate::{AocError, Part, Solution};
+use std::fmt;
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// ## What was measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+  Parse,
+  Solve(Part),
+}
+
+impl fmt::Display for Stage {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Stage::Parse => write!(f, "parse"),
+      Stage::Solve(Part::One) => write!(f, "part_1"),
+      Stage::Solve(Part::Two) => write!(f, "part_2"),
+    }
+  }
+}
+
+impl FromStr for Stage {
+  type Err = AocError;
+
+  fn from_str(stage: &str) -> Result<Stage, AocError> {
+    match stage {
+      "parse" => Ok(Stage::Parse),
+      "part_1" => Ok(Stage::Solve(Part::One)),
+      "part_2" => Ok(Stage::Solve(Part::Two)),
+      _ => Err(AocError::parse(format!("unknown stage {:?}", stage))),
+    }
+  }
+}
+
+/// ## Summary of a series of samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+  pub samples: usize,
+  pub min: Duration,
+  pub median: Duration,
+  pub mean: Duration,
+  pub stddev: Duration,
+}
+
+impl Stats {
+  /// ### Summarize samples, there should be at least one
+  ///
+  /// ### Example
+  /// ```
+  /// use std::time::Duration;
+  ///
+  /// let samples: Vec<Duration> = vec![ 4, 2, 9, 5 ].into_iter().map( Duration::from_millis ).collect();
+  /// let stats = utils::bench::Stats::from_samples( &samples );
+  ///
+  /// assert_eq!( stats.min, Duration::from_millis( 2 ) );
+  /// assert_eq!( stats.median, Duration::from_micros( 4500 ) );
+  /// assert_eq!( stats.mean, Duration::from_millis( 5 ) );
+  /// assert_eq!( stats.stddev.as_millis(), 2 );
+  /// ```
+  pub fn from_samples(samples: &[Duration]) -> Stats {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+
+    let count = sorted.len();
+    let median = if count.is_multiple_of(2) {
+      (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+    } else {
+      sorted[count / 2]
+    };
+
+    let nanos: Vec<f64> = sorted.iter().map(|sample| sample.as_nanos() as f64).collect();
+    let mean = nanos.iter().sum::<f64>() / count as f64;
+    let variance = if count > 1 {
+      nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count - 1) as f64
+    } else {
+      0.0
+    };
+
+    Stats {
+      samples: count,
+      min: sorted[0],
+      median,
+      mean: Duration::from_nanos(mean.round() as u64),
+      stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+    }
+  }
+}
+
+/// ## Time and memory used by a stage
+#[derive(Debug, Clone)]
+pub struct Measurement {
+  pub stage: Stage,
+  pub stats: Stats,
+  /// Memory used by a single iteration, only counted when the
+  /// `alloc::CountingAllocator` is installed
+  pub memory: AllocStats,
+}
+
+/// ## All measurements of a single day
+#[derive(Debug)]
+pub struct Benchmark {
+  pub day: u8,
+  pub measurements: Vec<Measurement>,
+}
+
+/// ## Benchmark parsing and both parts of a solution
+///
+/// Every stage is run `iterations` times, the parts all reuse the result of a
+/// single parse.
+///
+/// ### Errors
+/// Fails if the input can't be parsed or one of the parts fails.
+pub fn run<S: Solution>(input: &str, iterations: usize) -> Result<Benchmark, AocError> {
+  let iterations = iterations.max(1);
+
+  let parse = sample(Stage::Parse, iterations, || {
+    S::parse(black_box(input)).map(drop)
+  })?;
+  let parsed = S::parse(input)?;
+  let part_1 = sample(Stage::Solve(Part::One), iterations, || {
+    S::part_1(black_box(&parsed)).map(drop)
+  })?;
+  let part_2 = sample(Stage::Solve(Part::Two), iterations, || {
+    S::part_2(black_box(&parsed)).map(drop)
+  })?;
+
+  Ok(Benchmark {
+    day: S::DAY,
+    measurements: vec![parse, part_1, part_2],
+  })
+}
+
+fn sample<F>(stage: Stage, iterations: usize, mut f: F) -> Result<Measurement, AocError>
+where
+  F: FnMut() -> Result<(), AocError>,
+{
+  let mut samples = Vec::with_capacity(iterations);
+  let mut memory = AllocStats::default();
+  for _ in 0..iterations {
+    let start = Instant::now();
+    let (result, used) = alloc::measure(&mut f);
+    result?;
+    samples.push(start.elapsed());
+    memory = used;
+  }
+  Ok(Measurement {
+    stage,
+    stats: Stats::from_samples(&samples),
+    memory,
+  })
+}
+
+/// ## Human readable duration with a fitting unit
+///
+/// ### Example
+/// ```
+/// use std::time::Duration;
+///
+/// assert_eq!( utils::bench::format_duration( Duration::from_nanos( 512 ) ), "512ns" );
+/// assert_eq!( utils::bench::format_duration( Duration::from_nanos( 61_932 ) ), "61.93µs" );
+/// assert_eq!( utils::bench::format_duration( Duration::from_millis( 7_360 ) ), "7.36s" );
+/// ```
+pub fn format_duration(duration: Duration) -> String {
+  let nanos = duration.as_nanos() as f64;
+  if nanos < 1e3 {
+    format!("{}ns", nanos)
+  } else if nanos < 1e6 {
+    format!("{:.2}µs", nanos / 1e3)
+  } else if nanos < 1e9 {
+    format!("{:.2}ms", nanos / 1e6)
+  } else {
+    format!("{:.2}s", nanos / 1e9)
+  }
+}
+
+/// ## Render benchmarks as an aligned table
+///
+/// Memory columns are only added when allocations are being counted.
+pub fn table(benchmarks: &[Benchmark]) -> String {
+  let memory = alloc::is_counting();
+  let mut table = format!(
+    "{:>3}  {:<6}  {:>7}  {:>10}  {:>10}  {:>10}  {:>10}",
+    "day", "stage", "samples", "min", "median", "mean", "stddev"
+  );
+  if memory {
+    table.push_str(&format!("  {:>12}  {:>12}  {:>8}", "peak", "allocated", "allocs"));
+  }
+  table.push('\n');
+
+  for benchmark in benchmarks {
+    for Measurement {
+      stage,
+      stats,
+      memory: used,
+    } in &benchmark.measurements
+    {
+      table.push_str(&format!(
+        "{:>3}  {:<6}  {:>7}  {:>10}  {:>10}  {:>10}  {:>10}",
+        benchmark.day,
+        stage.to_string(),
+        stats.samples,
+        format_duration(stats.min),
+        format_duration(stats.median),
+        format_duration(stats.mean),
+        format_duration(stats.stddev)
+      ));
+      if memory {
+        table.push_str(&format!(
+          "  {:>12}  {:>12}  {:>8}",
+          alloc::format_bytes(used.peak_bytes),
+          alloc::format_bytes(used.total_bytes),
+          used.allocations
+        ));
+      }
+      table.push('\n');
+    }
+  }
+  table
+}
+
+/// ## Render benchmarks as JSON, with all durations in nanoseconds
+///
+/// Memory fields are zero unless allocations are being counted.
+///
+/// ### Example
+/// ```
+/// use std::time::Duration;
+/// use utils::alloc::AllocStats;
+/// use utils::bench::{Benchmark, Measurement, Stage, Stats};
+///
+/// let stats = Stats::from_samples( &[ Duration::from_nanos( 10 ) ] );
+/// let memory = AllocStats { peak_bytes: 64, total_bytes: 96, allocations: 2 };
+/// let measurement = Measurement { stage: Stage::Parse, stats, memory };
+/// let benchmark = Benchmark { day: 1, measurements: vec![ measurement ] };
+///
+/// assert_eq!(
+///   utils::bench::json( &[ benchmark ] ),
+///   r#"[{"day":1,"stage":"parse","samples":1,"min_ns":10,"median_ns":10,"mean_ns":10,"stddev_ns":0,"peak_bytes":64,"allocated_bytes":96,"allocations":2}]"#
+/// );
+/// ```
+pub fn json(benchmarks: &[Benchmark]) -> String {
+  let entries: Vec<String> = benchmarks
+    .iter()
+    .flat_map(|benchmark| {
+      benchmark.measurements.iter().map(move |measurement| {
+        let (stats, memory) = (&measurement.stats, &measurement.memory);
+        format!(
+          r#"{{"day":{},"stage":"{}","samples":{},"min_ns":{},"median_ns":{},"mean_ns":{},"stddev_ns":{},"peak_bytes":{},"allocated_bytes":{},"allocations":{}}}"#,
+          benchmark.day,
+          measurement.stage,
+          stats.samples,
+          stats.min.as_nanos(),
+          stats.median.as_nanos(),
+          stats.mean.as_nanos(),
+          stats.stddev.as_nanos(),
+          memory.peak_bytes,
+          memory.total_bytes,
+          memory.allocations
+        )
+      })
+    })
+    .collect();
+  format!("[{}]", entries.join(","))
+}
@@ -0,0 +1,77 @@
+//! # Cycle detection
+//!
+//! Several puzzles step a state far more often than is feasible, but the
+//! states start repeating long before that.  Every state seen is hashed, so
+//! the first repeat gives away the cycle and the steps left can be skipped.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// ## A repeating sequence of states
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+  /// Steps before the first state of the cycle
+  pub start: usize,
+  /// Steps after which a state of the cycle comes back
+  pub length: usize,
+}
+
+impl Cycle {
+  /// ## Step of the first `limit` steps that leads to the same state as `step`
+  ///
+  /// ### Example
+  /// ```
+  /// let cycle = utils::cycle::Cycle { start: 2, length: 3 };
+  /// assert_eq!( cycle.earliest( 1 ), 1 );
+  /// assert_eq!( cycle.earliest( 4 ), 4 );
+  /// assert_eq!( cycle.earliest( 5 ), 2 );
+  /// assert_eq!( cycle.earliest( 1_000_000_000 ), 4 );
+  /// ```
+  pub fn earliest(&self, step: usize) -> usize {
+    if step < self.start {
+      step
+    } else {
+      self.start + (step - self.start) % self.length
+    }
+  }
+}
+
+/// ## The state after a number of steps
+///
+/// Steps the state, remembering every state seen, until either `steps` steps
+/// are done or a state repeats.  A repeat reveals the cycle, the state after
+/// `steps` steps is then one already seen.
+///
+/// ### Example
+/// ```
+/// // The sequence 1, 2, 4, 8, 16, 32, 64, 28, 56, 12, 24, 48, 96, 92, 84, 68, 36, 72, 44, 88, 76, 52, 4, ...
+/// let double = |n: &u32| n * 2 % 100;
+///
+/// assert_eq!( utils::cycle::nth( 1, double, 6 ), 64 );
+/// assert_eq!( utils::cycle::nth( 1, double, 22 ), 4 );
+/// assert_eq!( utils::cycle::nth( 1, double, 1_000_000_000 ), 76 );
+/// ```
+pub fn nth<T, F>(initial: T, step: F, steps: usize) -> T
+where
+  T: Clone + Eq + Hash,
+  F: Fn(&T) -> T,
+{
+  let mut seen: HashMap<T, usize> = HashMap::new();
+  let mut states = vec![initial];
+
+  for done in 0..steps {
+    let state = &states[done];
+    if let Some(&start) = seen.get(state) {
+      let cycle = Cycle {
+        start,
+        length: done - start,
+      };
+      return states.swap_remove(cycle.earliest(steps));
+    }
+    seen.insert(state.clone(), done);
+
+    let next = step(state);
+    states.push(next);
+  }
+  states.pop().expect("the initial state is always there")
+}
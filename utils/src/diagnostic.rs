@@ -0,0 +1,109 @@
+use std::fmt;
+
+/// # A byte range in the puzzle input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+  pub start: usize,
+  pub end: usize,
+}
+
+impl Span {
+  pub fn new(start: usize, end: usize) -> Span {
+    Span { start, end }
+  }
+
+  /// ## Span covering all of `part`, which must be a slice of `whole`
+  ///
+  /// ### Example
+  /// ```
+  /// let input = "first\nsecond";
+  /// let second = input.lines().nth( 1 ).unwrap();
+  /// assert_eq!( utils::Span::of( input, second ), utils::Span::new( 6, 12 ) );
+  /// ```
+  pub fn of(whole: &str, part: &str) -> Span {
+    let start = part.as_ptr() as usize - whole.as_ptr() as usize;
+    Span::new(start, start + part.len())
+  }
+
+  /// ## Move a span that is relative to a line to be relative to the input
+  pub fn offset(self, by: usize) -> Span {
+    Span::new(self.start + by, self.end + by)
+  }
+}
+
+/// # Points out a span of the input together with a message
+///
+/// Renders the file name, line and column, followed by the offending line
+/// with the span underlined.
+///
+/// ### Example
+/// ```
+/// let source = "#1 @ 1,3: 4x4\n#2 @ 3,x: 4x4\n";
+/// let diagnostic = utils::Diagnostic {
+///   file_name: "input.txt",
+///   source,
+///   span: utils::Span::new( 21, 22 ),
+///   message: "expected a number",
+/// };
+///
+/// assert_eq!( diagnostic.to_string(), "\
+/// error: expected a number
+///  --> input.txt:2:8
+///   |
+/// 2 | #2 @ 3,x: 4x4
+///   |        ^
+/// " );
+///
+/// // A span on the end of a CRLF line points just past the line
+/// let diagnostic = utils::Diagnostic {
+///   file_name: "input.txt",
+///   source: "+1\r\n+2\r\n",
+///   span: utils::Span::new( 3, 4 ),
+///   message: "expected a change",
+/// };
+///
+/// assert_eq!( diagnostic.to_string(), "\
+/// error: expected a change
+///  --> input.txt:1:3
+///   |
+/// 1 | +1
+///   |   ^
+/// " );
+/// ```
+pub struct Diagnostic<'a> {
+  pub file_name: &'a str,
+  pub source: &'a str,
+  pub span: Span,
+  pub message: &'a str,
+}
+
+impl<'a> fmt::Display for Diagnostic<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let start = self.span.start.min(self.source.len());
+    let line_start = self.source[..start].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = self.source[start..]
+      .find('\n')
+      .map_or(self.source.len(), |index| start + index);
+    let line = self.source[line_start..line_end].trim_end_matches('\r');
+    // A span on the carriage return of a CRLF line points past the trimmed line
+    let start = start.min(line_start + line.len());
+
+    let line_number = self.source[..line_start].matches('\n').count() + 1;
+    let column = self.source[line_start..start].chars().count() + 1;
+    let end = self.span.end.min(line_start + line.len()).max(start);
+    let width = self.source[start..end].chars().count().max(1);
+    let gutter = " ".repeat(line_number.to_string().len());
+
+    writeln!(f, "error: {}", self.message)?;
+    writeln!(f, "{}--> {}:{}:{}", gutter, self.file_name, line_number, column)?;
+    writeln!(f, "{} |", gutter)?;
+    writeln!(f, "{} | {}", line_number, line)?;
+    writeln!(
+      f,
+      "{} | {}{}",
+      gutter,
+      " ".repeat(column - 1),
+      "^".repeat(width)
+    )
+  }
+}
@@ -0,0 +1,289 @@
+//! # The device's instruction set
+//!
+//! The wrist device of the 2018 puzzles runs programs of sixteen opcodes
+//! operating on a handful of registers.  Every instruction has two inputs,
+//! `a` and `b`, and writes its result to register `c`.
+
+use crate::{AocError, Span};
+use std::fmt;
+use std::str::FromStr;
+
+/// ## Values of the registers of the device
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Registers(pub Vec<usize>);
+
+impl Registers {
+  /// ## Registers that all hold zero
+  pub fn new(count: usize) -> Registers {
+    Registers(vec![0; count])
+  }
+
+  pub fn get(&self, register: usize) -> Option<usize> {
+    self.0.get(register).copied()
+  }
+
+  /// ## Parse registers written as a list, like `[3, 2, 1, 1]`
+  ///
+  /// ### Example
+  /// ```
+  /// use utils::elfcode::Registers;
+  ///
+  /// assert_eq!( Registers::parse( "[3, 2, 1, 1]" ).unwrap(), Registers( vec![ 3, 2, 1, 1 ] ) );
+  ///
+  /// let error = Registers::parse( "[3, two, 1, 1]" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "invalid register value \"two\" (bytes 4..7)" );
+  /// ```
+  pub fn parse(list: &str) -> Result<Registers, AocError> {
+    let values = list
+      .trim()
+      .strip_prefix('[')
+      .and_then(|list| list.strip_suffix(']'))
+      .ok_or_else(|| AocError::parse(format!("invalid registers \"{}\"", list)))?;
+
+    values
+      .split(',')
+      .map(|value| {
+        let value = value.trim();
+        value.parse().map_err(|_| {
+          AocError::parse_at(
+            Span::of(list, value),
+            format!("invalid register value \"{}\"", value),
+          )
+        })
+      })
+      .collect::<Result<_, _>>()
+      .map(Registers)
+  }
+}
+
+impl fmt::Display for Registers {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let values: Vec<String> = self.0.iter().map(usize::to_string).collect();
+    write!(f, "[{}]", values.join(", "))
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Opcode {
+  Addr,
+  Addi,
+  Mulr,
+  Muli,
+  Banr,
+  Bani,
+  Borr,
+  Bori,
+  Setr,
+  Seti,
+  Gtir,
+  Gtri,
+  Gtrr,
+  Eqir,
+  Eqri,
+  Eqrr,
+}
+
+impl Opcode {
+  pub const ALL: [Opcode; 16] = [
+    Opcode::Addr,
+    Opcode::Addi,
+    Opcode::Mulr,
+    Opcode::Muli,
+    Opcode::Banr,
+    Opcode::Bani,
+    Opcode::Borr,
+    Opcode::Bori,
+    Opcode::Setr,
+    Opcode::Seti,
+    Opcode::Gtir,
+    Opcode::Gtri,
+    Opcode::Gtrr,
+    Opcode::Eqir,
+    Opcode::Eqri,
+    Opcode::Eqrr,
+  ];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      Opcode::Addr => "addr",
+      Opcode::Addi => "addi",
+      Opcode::Mulr => "mulr",
+      Opcode::Muli => "muli",
+      Opcode::Banr => "banr",
+      Opcode::Bani => "bani",
+      Opcode::Borr => "borr",
+      Opcode::Bori => "bori",
+      Opcode::Setr => "setr",
+      Opcode::Seti => "seti",
+      Opcode::Gtir => "gtir",
+      Opcode::Gtri => "gtri",
+      Opcode::Gtrr => "gtrr",
+      Opcode::Eqir => "eqir",
+      Opcode::Eqri => "eqri",
+      Opcode::Eqrr => "eqrr",
+    }
+  }
+
+  /// ## Value the opcode writes to register `c`
+  ///
+  /// Returns `None` when an input refers to a register that doesn't exist.
+  /// Additions and multiplications wrap around, like the registers of a
+  /// machine would.
+  ///
+  /// ### Example
+  /// ```
+  /// use utils::elfcode::{Opcode, Registers};
+  ///
+  /// let registers = Registers( vec![ 3, 2, 1, 1 ] );
+  /// assert_eq!( Opcode::Mulr.apply( &registers, 2, 1 ), Some( 2 ) );
+  /// assert_eq!( Opcode::Addi.apply( &registers, 2, 1 ), Some( 2 ) );
+  /// assert_eq!( Opcode::Seti.apply( &registers, 2, 1 ), Some( 2 ) );
+  /// assert_eq!( Opcode::Gtir.apply( &registers, 2, 1 ), Some( 0 ) );
+  /// assert_eq!( Opcode::Addr.apply( &registers, 2, 9 ), None );
+  ///
+  /// let registers = Registers( vec![ usize::MAX, 2 ] );
+  /// assert_eq!( Opcode::Addr.apply( &registers, 0, 1 ), Some( 1 ) );
+  /// assert_eq!( Opcode::Addi.apply( &registers, 0, usize::MAX ), Some( usize::MAX - 1 ) );
+  /// assert_eq!( Opcode::Mulr.apply( &registers, 0, 1 ), Some( usize::MAX - 1 ) );
+  /// assert_eq!( Opcode::Muli.apply( &registers, 0, usize::MAX ), Some( 1 ) );
+  /// ```
+  pub fn apply(self, registers: &Registers, a: usize, b: usize) -> Option<usize> {
+    let reg = |register: usize| registers.get(register);
+    let value = match self {
+      Opcode::Addr => reg(a)?.wrapping_add(reg(b)?),
+      Opcode::Addi => reg(a)?.wrapping_add(b),
+      Opcode::Mulr => reg(a)?.wrapping_mul(reg(b)?),
+      Opcode::Muli => reg(a)?.wrapping_mul(b),
+      Opcode::Banr => reg(a)? & reg(b)?,
+      Opcode::Bani => reg(a)? & b,
+      Opcode::Borr => reg(a)? | reg(b)?,
+      Opcode::Bori => reg(a)? | b,
+      Opcode::Setr => reg(a)?,
+      Opcode::Seti => a,
+      Opcode::Gtir => (a > reg(b)?) as usize,
+      Opcode::Gtri => (reg(a)? > b) as usize,
+      Opcode::Gtrr => (reg(a)? > reg(b)?) as usize,
+      Opcode::Eqir => (a == reg(b)?) as usize,
+      Opcode::Eqri => (reg(a)? == b) as usize,
+      Opcode::Eqrr => (reg(a)? == reg(b)?) as usize,
+    };
+    Some(value)
+  }
+
+  /// ## Execute the opcode, writing its result to register `c`
+  ///
+  /// Returns `None`, leaving the registers untouched, when the instruction
+  /// refers to a register that doesn't exist.
+  ///
+  /// ### Example
+  /// ```
+  /// use utils::elfcode::{Opcode, Registers};
+  ///
+  /// let mut registers = Registers( vec![ 3, 2, 1, 1 ] );
+  /// assert_eq!( Opcode::Mulr.execute( &mut registers, 2, 1, 2 ), Some( () ) );
+  /// assert_eq!( registers, Registers( vec![ 3, 2, 2, 1 ] ) );
+  ///
+  /// assert_eq!( Opcode::Seti.execute( &mut registers, 7, 0, 4 ), None );
+  /// assert_eq!( registers, Registers( vec![ 3, 2, 2, 1 ] ) );
+  /// ```
+  pub fn execute(self, registers: &mut Registers, a: usize, b: usize, c: usize) -> Option<()> {
+    let value = self.apply(registers, a, b)?;
+    *registers.0.get_mut(c)? = value;
+    Some(())
+  }
+}
+
+impl fmt::Display for Opcode {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+impl FromStr for Opcode {
+  type Err = AocError;
+
+  fn from_str(name: &str) -> Result<Opcode, AocError> {
+    Opcode::ALL
+      .iter()
+      .copied()
+      .find(|opcode| opcode.name() == name)
+      .ok_or_else(|| AocError::parse(format!("unknown opcode \"{}\"", name)))
+  }
+}
+
+/// ## An opcode with its inputs and output register
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+  pub opcode: Opcode,
+  pub a: usize,
+  pub b: usize,
+  pub c: usize,
+}
+
+impl Instruction {
+  /// ## Parse an instruction written as `addi 0 7 3`
+  ///
+  /// ### Example
+  /// ```
+  /// use utils::elfcode::{Instruction, Opcode};
+  ///
+  /// assert_eq!(
+  ///   Instruction::parse( "addi 0 7 3" ).unwrap(),
+  ///   Instruction { opcode: Opcode::Addi, a: 0, b: 7, c: 3 }
+  /// );
+  ///
+  /// let error = Instruction::parse( "addy 0 7 3" ).unwrap_err();
+  /// assert_eq!( error.to_string(), "unknown opcode \"addy\" (bytes 0..4)" );
+  ///
+  /// assert!( Instruction::parse( "addi 0 7" ).is_err() );
+  /// ```
+  pub fn parse(line: &str) -> Result<Instruction, AocError> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() != 4 {
+      return Err(AocError::parse(format!("invalid instruction \"{}\"", line)));
+    }
+
+    let number = |field: &str| {
+      field.parse().map_err(|_| {
+        AocError::parse_at(Span::of(line, field), format!("invalid number \"{}\"", field))
+      })
+    };
+    Ok(Instruction {
+      opcode: fields[0]
+        .parse()
+        .map_err(|err: AocError| err.within(Span::of(line, fields[0])))?,
+      a: number(fields[1])?,
+      b: number(fields[2])?,
+      c: number(fields[3])?,
+    })
+  }
+
+  /// ## Execute the instruction on the registers
+  ///
+  /// ### Example
+  /// ```
+  /// use utils::elfcode::{Instruction, Registers};
+  ///
+  /// let mut registers = Registers::new( 4 );
+  /// Instruction::parse( "seti 7 0 2" ).unwrap().execute( &mut registers ).unwrap();
+  /// Instruction::parse( "muli 2 3 1" ).unwrap().execute( &mut registers ).unwrap();
+  /// assert_eq!( registers, Registers( vec![ 0, 21, 7, 0 ] ) );
+  ///
+  /// assert!( Instruction::parse( "seti 7 0 4" ).unwrap().execute( &mut registers ).is_err() );
+  /// ```
+  ///
+  /// ### Errors
+  /// Fails if the instruction refers to a register that doesn't exist.
+  pub fn execute(&self, registers: &mut Registers) -> Result<(), AocError> {
+    self
+      .opcode
+      .execute(registers, self.a, self.b, self.c)
+      .ok_or_else(|| AocError::InvalidInput(format!("\"{}\" uses a register that doesn't exist", self)))
+  }
+}
+
+impl fmt::Display for Instruction {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{} {} {} {}", self.opcode, self.a, self.b, self.c)
+  }
+}
@@ -0,0 +1,129 @@
+use crate::{Diagnostic, Span};
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// # Errors shared by all days
+#[derive(Debug)]
+pub enum AocError {
+  /// The input couldn't be read
+  Io(io::Error),
+  /// Part of the input couldn't be parsed, `span` points into the input
+  /// when known
+  Parse {
+    span: Option<Span>,
+    message: String,
+  },
+  /// The input parsed fine but describes an impossible situation
+  InvalidInput(String),
+  /// The puzzle has no answer for this input
+  NoSolution(String),
+}
+
+impl AocError {
+  /// ## Create a parse error without location
+  pub fn parse<M: Into<String>>(message: M) -> AocError {
+    AocError::Parse {
+      span: None,
+      message: message.into(),
+    }
+  }
+
+  /// ## Create a parse error pointing at a span of the input
+  pub fn parse_at<M: Into<String>>(span: Span, message: M) -> AocError {
+    AocError::Parse {
+      span: Some(span),
+      message: message.into(),
+    }
+  }
+
+  /// ## Place a parse error for a single line inside the whole input
+  ///
+  /// A span relative to the line is moved to be relative to the input, a
+  /// parse error without span points at the entire line.  Other errors are
+  /// returned unchanged.
+  ///
+  /// ### Example
+  /// ```
+  /// let line = utils::Span::new( 10, 20 );
+  ///
+  /// let error = utils::AocError::parse( "invalid claim" ).within( line );
+  /// assert_eq!( error.to_string(), "invalid claim (bytes 10..20)" );
+  ///
+  /// let error = utils::AocError::parse_at( utils::Span::new( 2, 3 ), "not a number" ).within( line );
+  /// assert_eq!( error.to_string(), "not a number (bytes 12..13)" );
+  /// ```
+  pub fn within(self, line: Span) -> AocError {
+    match self {
+      AocError::Parse { span, message } => AocError::Parse {
+        span: Some(span.map_or(line, |span| span.offset(line.start))),
+        message,
+      },
+      other => other,
+    }
+  }
+
+  /// ## Describe the error, pointing into the input when possible
+  ///
+  /// ### Example
+  /// ```
+  /// let source = "+1\n+x\n";
+  /// let error = utils::AocError::parse_at( utils::Span::new( 3, 5 ), "invalid integer" );
+  ///
+  /// assert_eq!( error.render( "input.txt", source ), "\
+  /// error: invalid integer
+  ///  --> input.txt:2:1
+  ///   |
+  /// 2 | +x
+  ///   | ^^
+  /// " );
+  /// ```
+  pub fn render(&self, file_name: &str, source: &str) -> String {
+    match self {
+      AocError::Parse {
+        span: Some(span),
+        message,
+      } => Diagnostic {
+        file_name,
+        source,
+        span: *span,
+        message,
+      }
+      .to_string(),
+      other => format!("error: {}\n", other),
+    }
+  }
+}
+
+impl fmt::Display for AocError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      AocError::Io(err) => write!(f, "couldn't read input: {}", err),
+      AocError::Parse {
+        span: Some(span),
+        message,
+      } => write!(f, "{} (bytes {}..{})", message, span.start, span.end),
+      AocError::Parse {
+        span: None,
+        message,
+      } => write!(f, "{}", message),
+      AocError::InvalidInput(message) => write!(f, "invalid input: {}", message),
+      AocError::NoSolution(message) => write!(f, "no solution: {}", message),
+    }
+  }
+}
+
+impl Error for AocError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      AocError::Io(err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+impl From<io::Error> for AocError {
+  fn from(err: io::Error) -> AocError {
+    AocError::Io(err)
+  }
+}
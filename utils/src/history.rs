@@ -0,0 +1,269 @@
+//! # Benchmark history
+//!
+//! Every benchmark run is appended to a tab separated history file, one line
+//! per day and stage.  Runs can then be compared to detect regressions.
+
+use crate::bench::{Stage, Stats};
+use crate::{AocError, Span};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+const HEADER: &str = "run\trevision\tinput_hash\tday\tstage\tsamples\tmin_ns\tmedian_ns\tmean_ns\tstddev_ns";
+
+/// ## A single measurement of a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+  /// Name of the run, all records of a run share it
+  pub run: String,
+  pub revision: String,
+  pub input_hash: String,
+  pub day: u8,
+  pub stage: Stage,
+  pub stats: Stats,
+}
+
+impl Record {
+  /// ### Parse a line of the history file
+  ///
+  /// ### Example
+  /// ```
+  /// use utils::history::Record;
+  ///
+  /// let line = "nightly\t3bef3ee\tcbf29ce484222325\t1\tpart_2\t5\t10\t12\t13\t1";
+  /// let record = Record::parse( line ).unwrap();
+  /// assert_eq!( record.day, 1 );
+  /// assert_eq!( record.stats.median.as_nanos(), 12 );
+  /// assert_eq!( record.to_line(), line );
+  ///
+  /// assert!( Record::parse( "nightly\t3bef3ee" ).is_err() );
+  /// ```
+  pub fn parse(line: &str) -> Result<Record, AocError> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() != 10 {
+      return Err(AocError::parse(format!(
+        "expected 10 tab separated fields, found {}",
+        fields.len()
+      )));
+    }
+
+    let number = |index: usize| {
+      fields[index]
+        .parse::<u64>()
+        .map_err(|_| AocError::parse_at(Span::of(line, fields[index]), "expected a number"))
+    };
+    let nanos = |index: usize| number(index).map(Duration::from_nanos);
+
+    Ok(Record {
+      run: fields[0].to_string(),
+      revision: fields[1].to_string(),
+      input_hash: fields[2].to_string(),
+      day: fields[3]
+        .parse()
+        .map_err(|_| AocError::parse_at(Span::of(line, fields[3]), "expected a day"))?,
+      stage: fields[4]
+        .parse()
+        .map_err(|err: AocError| err.within(Span::of(line, fields[4])))?,
+      stats: Stats {
+        samples: number(5)? as usize,
+        min: nanos(6)?,
+        median: nanos(7)?,
+        mean: nanos(8)?,
+        stddev: nanos(9)?,
+      },
+    })
+  }
+
+  pub fn to_line(&self) -> String {
+    format!(
+      "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+      self.run,
+      self.revision,
+      self.input_hash,
+      self.day,
+      self.stage,
+      self.stats.samples,
+      self.stats.min.as_nanos(),
+      self.stats.median.as_nanos(),
+      self.stats.mean.as_nanos(),
+      self.stats.stddev.as_nanos()
+    )
+  }
+}
+
+/// ## Hash of a puzzle input, stable across platforms and compiler versions
+///
+/// ### Example
+/// ```
+/// assert_eq!( utils::history::input_hash( "" ), "cbf29ce484222325" );
+/// assert_ne!( utils::history::input_hash( "+1\n" ), utils::history::input_hash( "+2\n" ) );
+/// ```
+pub fn input_hash(input: &str) -> String {
+  // 64-bit FNV-1a
+  let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+    (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+  });
+  format!("{:016x}", hash)
+}
+
+/// ## Append records to the history file, creating it when needed
+pub fn append(path: &Path, records: &[Record]) -> Result<(), AocError> {
+  let is_new = !path.exists();
+  let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+  if is_new {
+    writeln!(file, "{}", HEADER)?;
+  }
+  for record in records {
+    writeln!(file, "{}", record.to_line())?;
+  }
+  Ok(())
+}
+
+/// ## Parse the content of a history file
+///
+/// The error of a malformed line points into the content.
+pub fn parse(content: &str) -> Result<Vec<Record>, AocError> {
+  content
+    .lines()
+    .filter(|line| !line.is_empty() && *line != HEADER)
+    .map(|line| Record::parse(line).map_err(|err| err.within(Span::of(content, line))))
+    .collect()
+}
+
+/// ## Check that no run in the history has a name yet
+///
+/// Records are grouped into runs by name, so reusing a name would merge a new
+/// run into an old one.
+///
+/// ### Example
+/// ```
+/// use utils::history::{self, Record};
+///
+/// let records = vec![ Record::parse( "v1\taaaaaaa\tcbf29ce484222325\t1\tpart_1\t5\t10\t100\t100\t1" ).unwrap() ];
+/// assert!( history::ensure_new_run( &records, "v2" ).is_ok() );
+///
+/// let error = history::ensure_new_run( &records, "v1" ).unwrap_err();
+/// assert_eq!( error.to_string(), "invalid input: the benchmark history already has a run named \"v1\"" );
+/// ```
+///
+/// ### Errors
+/// Fails if a record of the history belongs to a run with that name.
+pub fn ensure_new_run(records: &[Record], run: &str) -> Result<(), AocError> {
+  if records.iter().any(|record| record.run == run) {
+    return Err(AocError::InvalidInput(format!(
+      "the benchmark history already has a run named {:?}",
+      run
+    )));
+  }
+  Ok(())
+}
+
+/// ## Median of a stage in the latest and in a baseline run
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+  pub day: u8,
+  pub stage: Stage,
+  pub baseline_run: String,
+  pub baseline: Duration,
+  pub current: Duration,
+}
+
+impl Comparison {
+  /// ### Relative change of the median, `0.1` is 10% slower
+  pub fn change(&self) -> f64 {
+    let baseline = self.baseline.as_nanos() as f64;
+    (self.current.as_nanos() as f64 - baseline) / baseline.max(1.0)
+  }
+
+  pub fn is_regression(&self, threshold: f64) -> bool {
+    self.change() > threshold
+  }
+}
+
+/// ## Compare the latest run with earlier runs
+///
+/// Without a name every stage of the latest run is compared with the previous
+/// measurement of that stage.  Otherwise it is compared with the most recent
+/// run with that name, or whose revision starts with it.  Only stages measured
+/// on the same input are compared.
+///
+/// Returns the name of the latest run along with the comparisons.
+///
+/// ### Example
+/// ```
+/// use utils::history::{self, Record};
+///
+/// let records: Vec<Record> = vec![
+///   "v1\taaaaaaa\tcbf29ce484222325\t1\tpart_1\t5\t10\t100\t100\t1",
+///   "v2\tbbbbbbb\tcbf29ce484222325\t1\tpart_1\t5\t10\t125\t125\t1",
+///   "v2\tbbbbbbb\tcbf29ce484222325\t1\tpart_2\t5\t10\t300\t300\t1",
+///   "v3\tccccccc\tcbf29ce484222325\t1\tpart_1\t5\t10\t105\t105\t1",
+/// ].into_iter().map( |line| Record::parse( line ).unwrap() ).collect();
+///
+/// let ( current, comparisons ) = history::compare( &records, None ).unwrap();
+/// assert_eq!( current, "v3" );
+/// assert_eq!( comparisons.len(), 1 );
+/// assert_eq!( comparisons[0].baseline_run, "v2" );
+/// assert!( !comparisons[0].is_regression( 0.1 ) );
+///
+/// let ( _, comparisons ) = history::compare( &records, Some( "aaa" ) ).unwrap();
+/// assert_eq!( comparisons[0].baseline_run, "v1" );
+/// assert!( comparisons[0].is_regression( 0.01 ) );
+/// assert!( !comparisons[0].is_regression( 0.1 ) );
+///
+/// assert!( history::compare( &records, Some( "v4" ) ).is_err() );
+/// ```
+///
+/// ### Errors
+/// Fails if the history is empty or the named baseline doesn't exist.
+pub fn compare(
+  records: &[Record],
+  baseline: Option<&str>,
+) -> Result<(String, Vec<Comparison>), AocError> {
+  let current = &records
+    .last()
+    .ok_or_else(|| AocError::InvalidInput("the benchmark history is empty".to_string()))?
+    .run;
+  let earlier: Vec<&Record> = records
+    .iter()
+    .filter(|record| record.run != *current)
+    .collect();
+
+  let baseline_run = match baseline {
+    None => None,
+    Some(name) => Some(
+      earlier
+        .iter()
+        .rev()
+        .find(|record| record.run == name || record.revision.starts_with(name))
+        .map(|record| &record.run)
+        .ok_or_else(|| AocError::InvalidInput(format!("no earlier run matches {:?}", name)))?,
+    ),
+  };
+
+  let comparisons = records
+    .iter()
+    .filter(|record| record.run == *current)
+    .filter_map(|record| {
+      earlier
+        .iter()
+        .rev()
+        .find(|other| {
+          baseline_run.is_none_or(|run| other.run == *run)
+            && other.day == record.day
+            && other.stage == record.stage
+            && other.input_hash == record.input_hash
+        })
+        .map(|other| Comparison {
+          day: record.day,
+          stage: record.stage,
+          baseline_run: other.run.clone(),
+          baseline: other.stats.median,
+          current: record.stats.median,
+        })
+    })
+    .collect();
+
+  Ok((current.clone(), comparisons))
+}
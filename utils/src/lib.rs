@@ -1,6 +1,43 @@
 use std::fs;
 use std::io::Error;
 
+pub mod alloc;
+mod answers;
+pub mod bench;
+pub mod cycle;
+mod diagnostic;
+pub mod elfcode;
+mod error;
+pub mod history;
+mod solution;
+
+pub use answers::{split_example, Answers, Verdict};
+pub use diagnostic::{Diagnostic, Span};
+pub use error::AocError;
+pub use solution::{run, Part, Report, Solution, Timed};
+
 pub fn load_file(path: &str) -> Result<String, Error> {
   fs::read_to_string(path)
 }
+
+/// ## Parse every line of the input
+///
+/// Parse errors of a line are placed inside the whole input, see
+/// `AocError::within`.
+///
+/// ### Example
+/// ```
+/// let parse = |line: &str| line.parse::<i32>().map_err( |_| utils::AocError::parse( "not a number" ) );
+///
+/// assert_eq!( utils::parse_lines( "1\n2", parse ).unwrap(), vec![ 1, 2 ] );
+/// assert_eq!( utils::parse_lines( "1\nx", parse ).unwrap_err().to_string(), "not a number (bytes 2..3)" );
+/// ```
+pub fn parse_lines<T, F>(input: &str, parse: F) -> Result<Vec<T>, AocError>
+where
+  F: Fn(&str) -> Result<T, AocError>,
+{
+  input
+    .lines()
+    .map(|line| parse(line).map_err(|err| err.within(Span::of(input, line))))
+    .collect()
+}
@@ -0,0 +1,142 @@
+use crate::alloc::{self, AllocStats};
+use crate::AocError;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// # A solution to one day of the puzzle
+///
+/// The raw input is parsed once into `Input`, which is then shared by both
+/// parts.  This allows tooling to time parsing and solving separately.
+/// Malformed input is reported as an `AocError` rather than a panic.
+///
+/// ### Example
+/// ```
+/// struct Sum;
+///
+/// impl utils::Solution for Sum {
+///   const DAY: u8 = 0;
+///   const TITLE: &'static str = "Sum";
+///
+///   type Input<'a> = Vec<i32>;
+///   type Output1 = i32;
+///   type Output2 = usize;
+///
+///   fn parse(input: &str) -> Result<Vec<i32>, utils::AocError> {
+///     utils::parse_lines(input, |line| {
+///       line.parse().map_err(|_| utils::AocError::parse("not a number"))
+///     })
+///   }
+///
+///   fn part_1(input: &Vec<i32>) -> Result<i32, utils::AocError> {
+///     Ok(input.iter().sum())
+///   }
+///
+///   fn part_2(input: &Vec<i32>) -> Result<usize, utils::AocError> {
+///     Ok(input.len())
+///   }
+/// }
+///
+/// let report = utils::run::<Sum>("1\n2\n3", &[utils::Part::One, utils::Part::Two]).unwrap();
+/// assert_eq!(report.parts[0].1.value.as_ref().unwrap(), "6");
+/// assert_eq!(report.parts[1].1.value.as_ref().unwrap(), "3");
+///
+/// assert!(utils::run::<Sum>("1\nx", &[utils::Part::One]).is_err());
+/// ```
+pub trait Solution {
+  /// Day of the advent calendar
+  const DAY: u8;
+  /// Title of the puzzle
+  const TITLE: &'static str;
+
+  /// Parsed puzzle input, which may borrow from the raw input
+  type Input<'a>;
+  type Output1: Display;
+  type Output2: Display;
+
+  fn parse(input: &str) -> Result<Self::Input<'_>, AocError>;
+  fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, AocError>;
+  fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, AocError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+  One,
+  Two,
+}
+
+impl Part {
+  pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    match self {
+      Part::One => write!(f, "Part 1"),
+      Part::Two => write!(f, "Part 2"),
+    }
+  }
+}
+
+/// ## A value together with the time and memory it took to compute it
+#[derive(Debug)]
+pub struct Timed<T> {
+  pub value: T,
+  pub elapsed: Duration,
+  /// Only counted when the `alloc::CountingAllocator` is installed
+  pub memory: AllocStats,
+}
+
+impl<T> Timed<T> {
+  pub fn measure<F: FnOnce() -> T>(f: F) -> Timed<T> {
+    let ((value, elapsed), memory) = alloc::measure(|| {
+      let start = Instant::now();
+      let value = f();
+      (value, start.elapsed())
+    });
+    Timed {
+      value,
+      elapsed,
+      memory,
+    }
+  }
+}
+
+/// ## Outcome of running a solution on an input
+#[derive(Debug)]
+pub struct Report {
+  pub parse: Timed<()>,
+  pub parts: Vec<(Part, Timed<Result<String, AocError>>)>,
+}
+
+/// ## Parse the input once and run the requested parts on it
+///
+/// Fails if the input can't be parsed, a failing part is reported in the
+/// `Report` so the other part still runs.
+pub fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Report, AocError> {
+  let Timed {
+    value: parsed,
+    elapsed,
+    memory,
+  } = Timed::measure(|| S::parse(input));
+  let parsed = parsed?;
+
+  let parts = parts
+    .iter()
+    .map(|&part| {
+      let answer = Timed::measure(|| match part {
+        Part::One => S::part_1(&parsed).map(|answer| answer.to_string()),
+        Part::Two => S::part_2(&parsed).map(|answer| answer.to_string()),
+      });
+      (part, answer)
+    })
+    .collect();
+
+  Ok(Report {
+    parse: Timed {
+      value: (),
+      elapsed,
+      memory,
+    },
+    parts,
+  })
+}